@@ -0,0 +1,178 @@
+use serde_json::{Map, Value};
+use std::{env, fmt::Write, fs, path::Path};
+
+// Vanilla's data generator report, `java -DbundlerMainClass=net.minecraft.data.Main -jar server.jar --reports`
+const BLOCKS_REPORT: &str = "src/files/blocks.json";
+
+struct BlockType {
+    name: String,
+    properties: Vec<(String, Vec<String>)>,
+    states: Vec<State>,
+    default_state: u32,
+}
+
+struct State {
+    id: u32,
+    properties: Vec<(String, String)>,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", BLOCKS_REPORT);
+
+    let report = fs::read_to_string(BLOCKS_REPORT).expect("Blocks report is missing");
+    let report: Map<String, Value> =
+        serde_json::from_str(&report).expect("Blocks report is not a JSON object");
+
+    let mut block_types: Vec<BlockType> = report
+        .iter()
+        .map(|(name, block)| parse_block(name, block))
+        .collect();
+    block_types.sort_by_key(|block_type| block_type.states[0].id);
+
+    // States are looked up by indexing, so the ids can't have any holes
+    let mut next_id = 0;
+    for block_type in &block_types {
+        for state in &block_type.states {
+            assert_eq!(
+                state.id, next_id,
+                "State ids of {} aren't contiguous",
+                block_type.name
+            );
+            next_id += 1;
+        }
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(
+        Path::new(&out_dir).join("blocks.rs"),
+        generate(&block_types),
+    )
+    .unwrap();
+}
+
+fn parse_block(name: &str, block: &Value) -> BlockType {
+    let properties: Vec<(String, Vec<String>)> = block["properties"]
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(property, values)| {
+                    let values = values
+                        .as_array()
+                        .unwrap_or_else(|| panic!("Values of {}.{} aren't a list", name, property))
+                        .iter()
+                        .map(|value| value.as_str().unwrap().to_owned())
+                        .collect();
+
+                    (property.clone(), values)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut default_state = None;
+    let mut states: Vec<State> = block["states"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{} has no states", name))
+        .iter()
+        .map(|state| {
+            let id = state["id"].as_u64().unwrap() as u32;
+            if state["default"].as_bool() == Some(true) {
+                assert!(default_state.is_none(), "{} has two default states", name);
+                default_state = Some(id);
+            }
+
+            // Keep the report's property order, not whatever order the state lists them in
+            let state_properties = &state["properties"];
+            let properties = properties
+                .iter()
+                .map(|(property, _)| {
+                    let value = state_properties[property]
+                        .as_str()
+                        .unwrap_or_else(|| panic!("State {} of {} has no {}", id, name, property));
+
+                    (property.clone(), value.to_owned())
+                })
+                .collect();
+
+            State { id, properties }
+        })
+        .collect();
+    states.sort_by_key(|state| state.id);
+
+    BlockType {
+        name: name.to_owned(),
+        properties,
+        default_state: default_state.unwrap_or_else(|| panic!("{} has no default state", name)),
+        states,
+    }
+}
+
+fn generate(block_types: &[BlockType]) -> String {
+    let mut code = String::new();
+
+    code.push_str("pub static BLOCK_STATES: &[Block] = &[\n");
+    for block_type in block_types {
+        for state in &block_type.states {
+            let properties: Vec<String> = state
+                .properties
+                .iter()
+                .map(|(property, value)| format!("({:?}, {:?})", property, value))
+                .collect();
+
+            writeln!(
+                code,
+                "    Block {{ id: {}, name: {:?}, properties: &[{}] }},",
+                state.id,
+                block_type.name,
+                properties.join(", ")
+            )
+            .unwrap();
+        }
+    }
+    code.push_str("];\n\n");
+
+    code.push_str("pub static BLOCK_TYPES: &[BlockType] = &[\n");
+    for block_type in block_types {
+        let properties: Vec<String> = block_type
+            .properties
+            .iter()
+            .map(|(property, values)| format!("({:?}, &{:?})", property, values))
+            .collect();
+
+        writeln!(
+            code,
+            "    BlockType {{ name: {:?}, properties: &[{}], default_state: {}, min_state_id: {}, max_state_id: {} }},",
+            block_type.name,
+            properties.join(", "),
+            block_type.default_state,
+            block_type.states[0].id,
+            block_type.states[block_type.states.len() - 1].id
+        )
+        .unwrap();
+    }
+    code.push_str("];\n\n");
+
+    code.push_str("fn block_type_index(name: &str) -> Option<usize> {\n    match name {\n");
+    for (index, block_type) in block_types.iter().enumerate() {
+        writeln!(code, "        {:?} => Some({}),", block_type.name, index).unwrap();
+    }
+    code.push_str("        _ => None,\n    }\n}\n\n");
+
+    // Every block gets a constant for its default state, named like vanilla's `Blocks` fields
+    for block_type in block_types {
+        let constant = block_type
+            .name
+            .trim_start_matches("minecraft:")
+            .to_uppercase();
+
+        writeln!(
+            code,
+            "pub static {}: &Block = &BLOCK_STATES[{}];",
+            constant, block_type.default_state
+        )
+        .unwrap();
+    }
+
+    code
+}
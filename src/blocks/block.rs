@@ -7,10 +7,12 @@ pub struct BlockPos {
     pub z: i32,
 }
 
+// A single block state, `id` is the state id the client knows it by
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Block {
     pub id: u32,
     pub name: &'static str,
+    pub properties: &'static [(&'static str, &'static str)],
 }
 
 impl Hash for Block {
@@ -27,31 +29,149 @@ impl PartialEq for Block {
 
 impl Eq for Block {}
 
-// TODO: implement all the blocks
-pub static AIR: &'static Block = &Block {
-    id: 0,
-    name: "minecraft:air",
-};
-pub static STONE: &'static Block = &Block {
-    id: 1,
-    name: "minecraft:stone",
-};
-pub static DIORITE: &'static Block = &Block {
-    id: 5,
-    name: "minecraft:diorite",
-};
-
-pub static GRASS_BLOCK: &'static Block = &Block {
-    id: 9,
-    name: "minecraft:grass_block",
-};
-
-pub static DIRT: &'static Block = &Block {
-    id: 10,
-    name: "minecraft:dirt",
-};
-
-pub static WATER: &'static Block = &Block {
-    id: 80,
-    name: "minecraft:water",
-};
+impl Block {
+    pub fn from_id(id: u32) -> Option<&'static Block> {
+        BLOCK_STATES.get(id as usize)
+    }
+
+    // Gives the block's default state
+    pub fn from_name(name: &str) -> Option<&'static Block> {
+        BlockType::from_name(name).map(BlockType::default_state)
+    }
+
+    pub fn block_type(&self) -> &'static BlockType {
+        BlockType::from_name(self.name).expect("Every state belongs to a generated block")
+    }
+
+    pub fn get_property(&self, name: &str) -> Option<&'static str> {
+        self.properties
+            .iter()
+            .find(|(property, _)| *property == name)
+            .map(|(_, value)| *value)
+    }
+
+    // Same block with one property changed, the others stay as they are
+    pub fn with_property(&self, name: &str, value: &str) -> Option<&'static Block> {
+        self.get_property(name)?;
+
+        let properties: Vec<(&str, &str)> = self
+            .properties
+            .iter()
+            .map(|&(property, current)| (property, if property == name { value } else { current }))
+            .collect();
+
+        self.block_type().with_properties(&properties)
+    }
+}
+
+// Everything the blocks report says about one block
+#[derive(Debug)]
+pub struct BlockType {
+    pub name: &'static str,
+    pub properties: &'static [(&'static str, &'static [&'static str])],
+    pub default_state: u32,
+    pub min_state_id: u32,
+    pub max_state_id: u32,
+}
+
+impl BlockType {
+    pub fn from_name(name: &str) -> Option<&'static BlockType> {
+        block_type_index(name).map(|index| &BLOCK_TYPES[index])
+    }
+
+    pub fn default_state(&self) -> &'static Block {
+        &BLOCK_STATES[self.default_state as usize]
+    }
+
+    pub fn states(&self) -> &'static [Block] {
+        &BLOCK_STATES[self.min_state_id as usize..=self.max_state_id as usize]
+    }
+
+    // Properties that aren't given keep the value they have in the default state
+    pub fn with_properties(&self, properties: &[(&str, &str)]) -> Option<&'static Block> {
+        let valid = properties.iter().all(|(name, value)| {
+            self.properties
+                .iter()
+                .any(|(property, values)| property == name && values.contains(value))
+        });
+        if !valid {
+            return None;
+        }
+
+        let default = self.default_state();
+        self.states().iter().find(|state| {
+            state.properties.iter().all(|(property, value)| {
+                let wanted = properties
+                    .iter()
+                    .find(|(name, _)| name == property)
+                    .map_or(default.get_property(property), |(_, value)| Some(*value));
+
+                wanted == Some(*value)
+            })
+        })
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/blocks.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_range(name: &str) -> (u32, u32, u32) {
+        let block_type = BlockType::from_name(name).unwrap();
+
+        (
+            block_type.min_state_id,
+            block_type.default_state,
+            block_type.max_state_id,
+        )
+    }
+
+    // Ids as the 1.19.4 client knows them
+    #[test]
+    fn known_state_ids() {
+        assert_eq!(state_range("minecraft:air"), (0, 0, 0));
+        assert_eq!(state_range("minecraft:stone"), (1, 1, 1));
+        assert_eq!(state_range("minecraft:diorite"), (4, 4, 4));
+        assert_eq!(state_range("minecraft:grass_block"), (8, 9, 9));
+        assert_eq!(state_range("minecraft:bedrock"), (79, 79, 79));
+        assert_eq!(state_range("minecraft:water"), (80, 80, 95));
+        assert_eq!(state_range("minecraft:lava"), (96, 96, 111));
+
+        assert_eq!(Block::from_name("minecraft:water").unwrap().id, 80);
+        assert_eq!(Block::from_id(4).unwrap().name, "minecraft:diorite");
+        assert_eq!(
+            Block::from_id(8).unwrap().get_property("snowy"),
+            Some("true")
+        );
+    }
+
+    #[test]
+    fn states_are_indexed_by_id() {
+        for (index, state) in BLOCK_STATES.iter().enumerate() {
+            assert_eq!(state.id as usize, index);
+            assert!(state.block_type().states().contains(state));
+        }
+    }
+
+    #[test]
+    fn properties_select_states() {
+        let water = Block::from_name("minecraft:water").unwrap();
+        assert_eq!(water.with_property("level", "15").unwrap().id, 95);
+        assert!(water.with_property("level", "16").is_none());
+        assert!(water.with_property("snowy", "true").is_none());
+    }
+
+    #[test]
+    fn report_is_complete() {
+        assert_eq!(state_range("minecraft:sand"), (112, 112, 112));
+        assert_eq!(state_range("minecraft:oak_log"), (123, 124, 125));
+        assert_eq!(
+            state_range("minecraft:decorated_pot"),
+            (23714, 23715, 23721)
+        );
+        assert_eq!(BLOCK_STATES.len(), 23722);
+        assert_eq!(BLOCK_TYPES.len(), 998);
+    }
+}
@@ -0,0 +1,433 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::log;
+use crate::tcp::chat::TextComponent;
+
+pub const DEFAULT_CONFIG_PATH: &str = "server.properties";
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub server_ip: String,
+    pub server_port: u16,
+    pub motd: TextComponent,
+    pub max_players: u32,
+    pub protocol_version: u32,
+    pub view_distance: u32,
+    pub simulation_distance: u32,
+    pub gamemode: GameMode,
+    pub hardcore: bool,
+    pub level_seed: i64,
+    pub level_type: String,
+    pub reduced_debug_info: bool,
+    pub enable_respawn_screen: bool,
+    pub spawn_x: i64,
+    pub spawn_y: i64,
+    pub spawn_z: i64,
+    pub network_compression_threshold: i32,
+    pub enable_encryption: bool,
+    pub online_mode: bool,
+    pub session_server: String,
+    pub keep_alive_timeout: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            server_ip: "127.0.0.1".to_owned(),
+            server_port: 25565,
+            motd: TextComponent::text("Powered by mars.rs"),
+            max_players: 20,
+            protocol_version: 762,
+            view_distance: 3,
+            simulation_distance: 10,
+            gamemode: GameMode::Creative,
+            hardcore: false,
+            level_seed: 0,
+            level_type: "minecraft:flat".to_owned(),
+            reduced_debug_info: false,
+            enable_respawn_screen: true,
+            spawn_x: 0,
+            spawn_y: 50,
+            spawn_z: 0,
+            network_compression_threshold: 256,
+            enable_encryption: true,
+            online_mode: false,
+            session_server: "https://sessionserver.mojang.com".to_owned(),
+            keep_alive_timeout: 30,
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn set(&mut self, key: &str, value: &str) -> io::Result<()> {
+        match key {
+            "server-ip" => self.server_ip = value.to_owned(),
+            "server-port" => self.server_port = parse(key, value)?,
+            "motd" => self.motd = parse(key, value)?,
+            "max-players" => self.max_players = parse(key, value)?,
+            "protocol-version" => self.protocol_version = parse(key, value)?,
+            "view-distance" => self.view_distance = parse(key, value)?,
+            "simulation-distance" => self.simulation_distance = parse(key, value)?,
+            "gamemode" => self.gamemode = parse(key, value)?,
+            "hardcore" => self.hardcore = parse(key, value)?,
+            "level-seed" => self.level_seed = parse(key, value)?,
+            "level-type" => self.level_type = value.to_owned(),
+            "reduced-debug-info" => self.reduced_debug_info = parse(key, value)?,
+            "enable-respawn-screen" => self.enable_respawn_screen = parse(key, value)?,
+            "spawn-x" => self.spawn_x = parse(key, value)?,
+            "spawn-y" => self.spawn_y = parse(key, value)?,
+            "spawn-z" => self.spawn_z = parse(key, value)?,
+            "network-compression-threshold" => {
+                self.network_compression_threshold = parse(key, value)?
+            }
+            "enable-encryption" => self.enable_encryption = parse(key, value)?,
+            "online-mode" => self.online_mode = parse(key, value)?,
+            "session-server" => self.session_server = value.to_owned(),
+            "keep-alive-timeout" => self.keep_alive_timeout = parse(key, value)?,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unknown setting \"{}\"", key),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_properties(&self) -> String {
+        let entries = [
+            ("server-ip", self.server_ip.clone()),
+            ("server-port", self.server_port.to_string()),
+            (
+                "motd",
+                // Plain text stays readable, anything styled is kept as JSON
+                if self.motd == TextComponent::text(&self.motd.text) {
+                    self.motd.text.clone()
+                } else {
+                    self.motd.to_json()
+                },
+            ),
+            ("max-players", self.max_players.to_string()),
+            ("protocol-version", self.protocol_version.to_string()),
+            ("view-distance", self.view_distance.to_string()),
+            ("simulation-distance", self.simulation_distance.to_string()),
+            ("gamemode", self.gamemode.to_string()),
+            ("hardcore", self.hardcore.to_string()),
+            ("level-seed", self.level_seed.to_string()),
+            ("level-type", self.level_type.clone()),
+            ("reduced-debug-info", self.reduced_debug_info.to_string()),
+            (
+                "enable-respawn-screen",
+                self.enable_respawn_screen.to_string(),
+            ),
+            ("spawn-x", self.spawn_x.to_string()),
+            ("spawn-y", self.spawn_y.to_string()),
+            ("spawn-z", self.spawn_z.to_string()),
+            (
+                "network-compression-threshold",
+                self.network_compression_threshold.to_string(),
+            ),
+            ("enable-encryption", self.enable_encryption.to_string()),
+            ("online-mode", self.online_mode.to_string()),
+            ("session-server", self.session_server.clone()),
+            ("keep-alive-timeout", self.keep_alive_timeout.to_string()),
+        ];
+
+        let mut output = String::from("# Mars server properties\n");
+        for (key, value) in entries {
+            output.push_str(&format!("{}={}\n", key, value));
+        }
+
+        output
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut config = ServerConfig::default();
+
+        if !path.exists() {
+            log::info!("Writing default settings to {}", path.display());
+            fs::write(path, config.to_properties())?;
+
+            return Ok(config);
+        }
+
+        for (n, line) in fs::read_to_string(path)?.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                log::warn!("Ignoring line {} of {}: {}", n + 1, path.display(), line);
+                continue;
+            };
+
+            if let Err(e) = config.set(key.trim(), value.trim()) {
+                log::warn!("Ignoring line {} of {}: {}", n + 1, path.display(), e);
+            }
+        }
+
+        Ok(config)
+    }
+
+    // Accepts `--config <path>` plus `--<key> <value>` or `--<key>=<value>` overrides
+    pub fn from_args(args: impl Iterator<Item = String>) -> io::Result<Self> {
+        let mut path = DEFAULT_CONFIG_PATH.to_owned();
+        let mut overrides = vec![];
+
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            let Some(key) = arg.strip_prefix("--") else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unexpected argument \"{}\"", arg),
+                ));
+            };
+
+            let (key, value) = match key.split_once('=') {
+                Some((key, value)) => (key.to_owned(), value.to_owned()),
+                None => match args.next() {
+                    Some(value) => (key.to_owned(), value),
+                    None => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("Missing value for \"--{}\"", key),
+                        ))
+                    }
+                },
+            };
+
+            if key == "config" {
+                path = value;
+            } else {
+                overrides.push((key, value));
+            }
+        }
+
+        let mut config = ServerConfig::load(Path::new(&path))?;
+        for (key, value) in overrides {
+            config.set(&key, &value)?;
+        }
+
+        Ok(config)
+    }
+
+    pub fn compression_threshold(&self) -> Option<u32> {
+        u32::try_from(self.network_compression_threshold).ok()
+    }
+
+    pub fn keep_alive_timeout(&self) -> Duration {
+        Duration::from_secs(self.keep_alive_timeout)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    Survival = 0,
+    Creative,
+    Adventure,
+    Spectator,
+}
+
+impl FromStr for GameMode {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "survival" | "0" => Ok(Self::Survival),
+            "creative" | "1" => Ok(Self::Creative),
+            "adventure" | "2" => Ok(Self::Adventure),
+            "spectator" | "3" => Ok(Self::Spectator),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Survival => "survival",
+            Self::Creative => "creative",
+            Self::Adventure => "adventure",
+            Self::Spectator => "spectator",
+        };
+
+        f.write_str(name)
+    }
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> io::Result<T> {
+    value.parse().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid value \"{}\" for \"{}\"", value, key),
+        )
+    })
+}
+
+static CONFIG: OnceLock<ServerConfig> = OnceLock::new();
+
+pub fn init_config(config: ServerConfig) {
+    if CONFIG.set(config).is_err() {
+        panic!("Server config was already initialized");
+    }
+}
+
+pub fn get_config() -> &'static ServerConfig {
+    CONFIG.get_or_init(ServerConfig::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn motd_takes_text_or_json() {
+        let mut config = ServerConfig::default();
+
+        config.set("motd", "A Minecraft Server").unwrap();
+        assert_eq!(config.motd, TextComponent::text("A Minecraft Server"));
+        assert!(config
+            .to_properties()
+            .contains("\nmotd=A Minecraft Server\n"));
+
+        config
+            .set("motd", r#"{"text": "A Minecraft Server", "color": "gold"}"#)
+            .unwrap();
+        assert_eq!(
+            config.motd,
+            TextComponent::text("A Minecraft Server").color("gold")
+        );
+        assert!(config
+            .to_properties()
+            .contains(r#"motd={"text":"A Minecraft Server","color":"gold"}"#));
+    }
+
+    #[test]
+    fn rejects_broken_motd() {
+        let mut config = ServerConfig::default();
+
+        assert!(config.set("motd", r#"{"text": "#).is_err());
+        assert_eq!(config.motd, ServerConfig::default().motd);
+    }
+
+    // A path in the temp dir that no other test uses, removed once the test is done
+    struct TempFile(std::path::PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "mars-{}-{}.properties",
+                std::process::id(),
+                name
+            ));
+            let _ = fs::remove_file(&path);
+
+            TempFile(path)
+        }
+
+        fn with(name: &str, contents: &str) -> Self {
+            let file = TempFile::new(name);
+            fs::write(&file.0, contents).unwrap();
+            file
+        }
+
+        fn arg(&self) -> String {
+            self.0.to_str().unwrap().to_owned()
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn writes_defaults_when_missing() {
+        let file = TempFile::new("missing");
+
+        let config = ServerConfig::load(&file.0).unwrap();
+        let defaults = ServerConfig::default().to_properties();
+        assert_eq!(config.to_properties(), defaults);
+        assert_eq!(fs::read_to_string(&file.0).unwrap(), defaults);
+
+        // What was written reads back the same
+        let config = ServerConfig::load(&file.0).unwrap();
+        assert_eq!(config.to_properties(), defaults);
+    }
+
+    #[test]
+    fn skips_bad_lines() {
+        let file = TempFile::with(
+            "bad-lines",
+            "# comment\n\nmax-players=5\nnot a setting\nview-distance=far\nunknown-key=1\n motd = Hello \n",
+        );
+
+        let config = ServerConfig::load(&file.0).unwrap();
+        assert_eq!(config.max_players, 5);
+        assert_eq!(config.view_distance, ServerConfig::default().view_distance);
+        assert_eq!(config.motd, TextComponent::text("Hello"));
+    }
+
+    #[test]
+    fn arguments_override_the_file() {
+        let file = TempFile::with("overrides", "max-players=5\nhardcore=true\n");
+
+        let config = ServerConfig::from_args(args(&[
+            "--config",
+            &file.arg(),
+            "--max-players",
+            "7",
+            "--motd=Hello there",
+            "--gamemode=survival",
+        ]))
+        .unwrap();
+
+        assert_eq!(config.max_players, 7);
+        assert_eq!(config.motd, TextComponent::text("Hello there"));
+        assert_eq!(config.gamemode, GameMode::Survival);
+        assert!(config.hardcore);
+    }
+
+    #[test]
+    fn config_argument_picks_the_file() {
+        let file = TempFile::new("config-path");
+
+        let config = ServerConfig::from_args(args(&[&format!("--config={}", file.arg())])).unwrap();
+        assert_eq!(config.server_port, 25565);
+        assert!(file.0.exists());
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        let file = TempFile::with("bad-arguments", "");
+        let config = file.arg();
+
+        let error = ServerConfig::from_args(args(&["--config", &config, "--no-such-key", "1"]))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(error.to_string().contains("no-such-key"));
+
+        let error = ServerConfig::from_args(args(&["--config", &config, "--max-players=many"]))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        assert!(ServerConfig::from_args(args(&["--config", &config, "--max-players"])).is_err());
+        assert!(ServerConfig::from_args(args(&["--config", &config, "max-players=1"])).is_err());
+    }
+}
@@ -1,12 +1,14 @@
 #![allow(dead_code)]
 
-use crate::tcp::AsyncWriteOwnExt;
+use crate::tcp::{AsyncReadOwnExt, AsyncWriteOwnExt};
 use std::io;
 use tcp::server::start_server;
 
 mod blocks;
+mod config;
 mod log;
 pub mod nbt;
+mod registry;
 mod tcp;
 
 type VarInt = u32;
@@ -40,11 +42,29 @@ impl Position {
 
         Ok(())
     }
-}
 
-// TODO: implement ReadFrom and Bytes() from mango
+    pub async fn read_from(reader: &mut impl AsyncReadOwnExt) -> io::Result<Self> {
+        let value = reader.read_i64().await?;
+
+        Ok(Position {
+            x: value >> 38,
+            y: value << 52 >> 52,
+            z: value << 26 >> 38,
+        })
+    }
+}
 
 #[tokio::main]
 async fn main() {
-    start_server("127.0.0.1", "25565").await;
+    let config = match config::ServerConfig::from_args(std::env::args().skip(1)) {
+        Ok(config) => config,
+        Err(e) => {
+            log::error!("Failed to load the server config: {}", e);
+            std::process::exit(1);
+        }
+    };
+    config::init_config(config);
+
+    let config = config::get_config();
+    start_server(&config.server_ip, config.server_port).await;
 }
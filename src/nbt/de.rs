@@ -0,0 +1,331 @@
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeOwned, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+use std::io;
+
+use super::{Error, Nbt, NbtCompound, ReadNbtExt};
+
+pub fn from_bytes<T: DeserializeOwned>(mut bytes: &[u8]) -> io::Result<T> {
+    from_compound(bytes.read_nbt()?)
+}
+
+pub fn from_compound<T: DeserializeOwned>(compound: NbtCompound<'static>) -> io::Result<T> {
+    Ok(T::deserialize(Deserializer(Nbt::Compound(compound)))?)
+}
+
+// Integer tags are accepted for any integer type they fit in, the same goes for floats.
+// Unsigned types read back the bits that the serializer stored in signed tags.
+pub struct Deserializer(Nbt<'static>);
+
+impl<'de> IntoDeserializer<'de, Error> for Nbt<'static> {
+    type Deserializer = Deserializer;
+
+    fn into_deserializer(self) -> Deserializer {
+        Deserializer(self)
+    }
+}
+
+impl Deserializer {
+    fn into_list(self) -> Vec<Nbt<'static>> {
+        match self.0 {
+            Nbt::ByteArray(value) => value.iter().copied().map(Nbt::Byte).collect(),
+            Nbt::List(value) => value,
+            Nbt::IntArray(value) => value.into_iter().map(Nbt::Int).collect(),
+            Nbt::LongArray(value) => value.into_iter().map(Nbt::Long).collect(),
+            _ => unreachable!("Only called on list-like tags"),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Nbt::Byte(value) => visitor.visit_i8(value as i8),
+            Nbt::Short(value) => visitor.visit_i16(value),
+            Nbt::Int(value) => visitor.visit_i32(value),
+            Nbt::Long(value) => visitor.visit_i64(value),
+            Nbt::Float(value) => visitor.visit_f32(value),
+            Nbt::Double(value) => visitor.visit_f64(value),
+            Nbt::String(value) => visitor.visit_string(value.into_owned()),
+            Nbt::Compound(value) => {
+                let entries = value
+                    .0
+                    .into_iter()
+                    .map(|(key, value)| (key.into_owned(), value));
+
+                let mut map = MapDeserializer::new(entries);
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+
+                Ok(value)
+            }
+            Nbt::ByteArray(_) | Nbt::List(_) | Nbt::IntArray(_) | Nbt::LongArray(_) => {
+                let mut seq = SeqDeserializer::new(self.into_list().into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Nbt::Byte(value) => visitor.visit_bool(value != 0),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Nbt::Byte(value) => visitor.visit_u8(value),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Nbt::Short(value) => visitor.visit_u16(value as u16),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Nbt::Int(value) => visitor.visit_u32(value as u32),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Nbt::Long(value) => visitor.visit_u64(value as u64),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Nbt::ByteArray(value) => visitor.visit_byte_buf(value.into_owned()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    // A tag being there at all means `Some`, missing fields are handled by serde itself
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Nbt::String(variant) => visitor.visit_enum(variant.into_owned().into_deserializer()),
+            Nbt::Compound(compound) if compound.0.len() == 1 => {
+                let (variant, value) = compound.0.into_iter().next().unwrap();
+                visitor.visit_enum(EnumAccess(variant.into_owned(), value))
+            }
+            _ => Err(Error::new(
+                "Enums are either a string or a compound with a single key",
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u128 f32 f64 char str string unit unit_struct seq tuple
+        tuple_struct map struct identifier
+    }
+}
+
+// Variants with data come as a compound holding the data under the variant's name
+struct EnumAccess(String, Nbt<'static>);
+
+struct VariantAccess(Nbt<'static>);
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess), Error> {
+        let variant = seed.deserialize(self.0.into_deserializer())?;
+
+        Ok((variant, VariantAccess(self.1)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(Deserializer(self.0))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(Deserializer(self.0), visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(Deserializer(self.0), visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use std::fmt::Debug;
+
+    use crate::nbt::{to_bytes, ByteArray, IntArray, LongArray};
+
+    use super::*;
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+        let bytes = to_bytes(&value).unwrap();
+        assert_eq!(from_bytes::<T>(&bytes).unwrap(), value);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Player {
+        name: String,
+        health: f32,
+        level: u32,
+        flying: bool,
+        position: (f64, f64, f64),
+        inventory: Vec<Item>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        id: String,
+        count: i8,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Mode {
+        Survival,
+        Spectating(i32),
+        Teleporting(i32, i32),
+        Riding { vehicle: String },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Holder<T> {
+        value: T,
+    }
+
+    #[test]
+    fn structs_round_trip() {
+        round_trip(Player {
+            name: "Notch".to_owned(),
+            health: 20.0,
+            level: u32::MAX,
+            flying: true,
+            position: (0.5, -64.0, 1e9),
+            inventory: vec![
+                Item {
+                    id: "minecraft:stone".to_owned(),
+                    count: 64,
+                },
+                Item {
+                    id: "minecraft:dirt".to_owned(),
+                    count: -1,
+                },
+            ],
+        });
+    }
+
+    #[test]
+    fn enums_round_trip() {
+        round_trip(Holder {
+            value: Mode::Survival,
+        });
+        round_trip(Holder {
+            value: Mode::Spectating(7),
+        });
+        round_trip(Holder {
+            value: Mode::Teleporting(1, -1),
+        });
+        round_trip(Holder {
+            value: Mode::Riding {
+                vehicle: "minecraft:boat".to_owned(),
+            },
+        });
+        round_trip(Holder {
+            value: vec![Mode::Spectating(1), Mode::Spectating(2)],
+        });
+    }
+
+    #[test]
+    fn options_round_trip() {
+        round_trip(Holder { value: Some(5i64) });
+        round_trip(Holder {
+            value: Some("text".to_owned()),
+        });
+
+        // `None` leaves the field out, which serde reads back as `None`
+        let bytes = to_bytes(&Holder::<Option<i64>> { value: None }).unwrap();
+        assert!((&bytes[..]).read_nbt().unwrap().get_long("value").is_none());
+        round_trip(Holder::<Option<i64>> { value: None });
+    }
+
+    #[test]
+    fn array_wrappers_round_trip() {
+        round_trip(Holder {
+            value: ByteArray(vec![0, 1, 255]),
+        });
+        round_trip(Holder {
+            value: IntArray(vec![i32::MIN, 0, i32::MAX]),
+        });
+        round_trip(Holder {
+            value: LongArray(vec![i64::MIN, 0, i64::MAX]),
+        });
+        round_trip(Holder {
+            value: IntArray(vec![]),
+        });
+
+        let bytes = to_bytes(&Holder {
+            value: LongArray(vec![3]),
+        })
+        .unwrap();
+        assert_eq!(
+            (&bytes[..]).read_nbt().unwrap().get_long_array("value"),
+            Some(&[3i64][..])
+        );
+    }
+}
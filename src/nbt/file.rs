@@ -0,0 +1,292 @@
+use flate2::{
+    read::{GzDecoder, ZlibDecoder},
+    write::{GzEncoder, ZlibEncoder},
+};
+use std::{
+    fmt::{self, Display},
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use super::{NbtCompound, NbtLimits, NbtType, ReadNbtExt, WriteNbtExt};
+
+// Vanilla gzips level.dat and playerdata, region files zlib their chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zlib,
+}
+
+impl Compression {
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0x1f, 0x8b, ..] => Some(Compression::Gzip),
+            // Deflate method, and the header checksum has to be a multiple of 31
+            [cmf, flg, ..] if cmf & 0x0f == 8 && u16::from_be_bytes([*cmf, *flg]) % 31 == 0 => {
+                Some(Compression::Zlib)
+            }
+            [id, ..] if *id == NbtType::Compound as u8 => Some(Compression::None),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum NbtFileError {
+    Io(io::Error),
+    Compression(io::Error),
+    Malformed(io::Error),
+    TooLarge(usize),
+}
+
+impl Display for NbtFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NbtFileError::Io(e) => write!(f, "I/O error: {}", e),
+            NbtFileError::Compression(e) => write!(f, "Corrupt compression: {}", e),
+            NbtFileError::Malformed(e) => write!(f, "Malformed NBT: {}", e),
+            NbtFileError::TooLarge(limit) => write!(f, "NBT is larger than {} bytes", limit),
+        }
+    }
+}
+
+impl std::error::Error for NbtFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NbtFileError::Io(e) | NbtFileError::Compression(e) | NbtFileError::Malformed(e) => {
+                Some(e)
+            }
+            NbtFileError::TooLarge(_) => None,
+        }
+    }
+}
+
+impl From<NbtFileError> for io::Error {
+    fn from(error: NbtFileError) -> Self {
+        match error {
+            NbtFileError::Io(e) | NbtFileError::Compression(e) | NbtFileError::Malformed(e) => e,
+            NbtFileError::TooLarge(_) => io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
+pub fn read_nbt_file(path: &Path) -> Result<(NbtCompound<'static>, Compression), NbtFileError> {
+    let bytes = fs::read(path).map_err(NbtFileError::Io)?;
+
+    decompress_nbt(&bytes)
+}
+
+// Region files hand over chunk payloads the same way, just without the file around them
+pub fn decompress_nbt(bytes: &[u8]) -> Result<(NbtCompound<'static>, Compression), NbtFileError> {
+    let compression = Compression::detect(bytes).ok_or_else(|| {
+        NbtFileError::Compression(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Unknown compression format",
+        ))
+    })?;
+
+    // Everything is inflated before parsing, so broken streams aren't blamed on the NBT
+    let limit = NbtLimits::FILE.max_size as u64 + 1;
+    let mut inflated = vec![];
+    let payload = match compression {
+        Compression::None => bytes,
+        Compression::Gzip => {
+            GzDecoder::new(bytes)
+                .take(limit)
+                .read_to_end(&mut inflated)
+                .map_err(NbtFileError::Compression)?;
+            &inflated
+        }
+        Compression::Zlib => {
+            ZlibDecoder::new(bytes)
+                .take(limit)
+                .read_to_end(&mut inflated)
+                .map_err(NbtFileError::Compression)?;
+            &inflated
+        }
+    };
+
+    // Inflating stops one byte past the limit, so anything longer is known to be too large
+    let max_size = NbtLimits::FILE.max_size;
+    if payload.len() > max_size {
+        return Err(NbtFileError::TooLarge(max_size));
+    }
+
+    let compound = (&payload[..])
+        .read_nbt_with_limits(NbtLimits::FILE)
+        .map_err(|e| match e.kind() {
+            // A small file can still hold more tags than fit in memory
+            io::ErrorKind::OutOfMemory => NbtFileError::TooLarge(max_size),
+            _ => NbtFileError::Malformed(e),
+        })?;
+
+    Ok((compound, compression))
+}
+
+pub fn compress_nbt(
+    compound: NbtCompound<'_>,
+    compression: Compression,
+) -> Result<Vec<u8>, NbtFileError> {
+    let level = flate2::Compression::default();
+
+    let bytes = match compression {
+        Compression::None => compound.pack().map_err(NbtFileError::Malformed)?,
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(vec![], level);
+            encoder
+                .write_compound(compound)
+                .map_err(NbtFileError::Malformed)?;
+            encoder.finish().map_err(NbtFileError::Compression)?
+        }
+        Compression::Zlib => {
+            let mut encoder = ZlibEncoder::new(vec![], level);
+            encoder
+                .write_compound(compound)
+                .map_err(NbtFileError::Malformed)?;
+            encoder.finish().map_err(NbtFileError::Compression)?
+        }
+    };
+
+    Ok(bytes)
+}
+
+// The file is written next to the target and renamed over it, so a crash never leaves half a file
+pub fn write_nbt_file(
+    path: &Path,
+    compound: NbtCompound<'_>,
+    compression: Compression,
+) -> Result<(), NbtFileError> {
+    let bytes = compress_nbt(compound, compression)?;
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let write = || -> io::Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+
+        fs::rename(&temp_path, path)
+    };
+
+    write().map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        NbtFileError::Io(e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level() -> NbtCompound<'static> {
+        let mut data = NbtCompound::default();
+        data.set_string("LevelName", "world");
+        data.set_long("RandomSeed", -4_172_144_997_902_289_642);
+        data.set_int_array("SpawnPos", vec![0, 64, 0]);
+
+        let mut level = NbtCompound::default();
+        level.set_compound("Data", data);
+        level
+    }
+
+    #[test]
+    fn every_compression_round_trips() {
+        for compression in [Compression::None, Compression::Gzip, Compression::Zlib] {
+            let bytes = compress_nbt(level(), compression).unwrap();
+            assert_eq!(Compression::detect(&bytes), Some(compression));
+
+            let (compound, detected) = decompress_nbt(&bytes).unwrap();
+            assert_eq!(detected, compression);
+            assert_eq!(compound.into_owned(), level());
+        }
+    }
+
+    #[test]
+    fn detects_headers() {
+        assert_eq!(
+            Compression::detect(&[0x1f, 0x8b, 0x08]),
+            Some(Compression::Gzip)
+        );
+        // The three header levels zlib writes, none, default and best
+        for header in [[0x78, 0x01], [0x78, 0x9c], [0x78, 0xda]] {
+            assert_eq!(Compression::detect(&header), Some(Compression::Zlib));
+        }
+        assert_eq!(Compression::detect(&[0x0a, 0x00]), Some(Compression::None));
+
+        assert_eq!(Compression::detect(&[0x78, 0x9d]), None);
+        assert_eq!(Compression::detect(&[0x08, 0x00]), None);
+        assert_eq!(Compression::detect(&[]), None);
+    }
+
+    #[test]
+    fn rejects_corrupt_streams() {
+        let mut bytes = compress_nbt(level(), Compression::Gzip).unwrap();
+        bytes.truncate(bytes.len() / 2);
+        assert!(matches!(
+            decompress_nbt(&bytes),
+            Err(NbtFileError::Compression(_))
+        ));
+
+        let bytes = compress_nbt(level(), Compression::None).unwrap();
+        assert!(matches!(
+            decompress_nbt(&bytes[..bytes.len() - 1]),
+            Err(NbtFileError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_oversized_files() {
+        let max_size = NbtLimits::FILE.max_size;
+        let mut bytes = vec![0; max_size + 1];
+        bytes[0] = NbtType::Compound as u8;
+        assert!(matches!(
+            decompress_nbt(&bytes),
+            Err(NbtFileError::TooLarge(limit)) if limit == max_size
+        ));
+
+        // Zeros shrink to almost nothing, only inflating shows the size
+        let mut encoder = ZlibEncoder::new(vec![], flate2::Compression::fast());
+        encoder.write_all(&bytes).unwrap();
+        let bytes = encoder.finish().unwrap();
+        assert!(matches!(
+            decompress_nbt(&bytes),
+            Err(NbtFileError::TooLarge(_))
+        ));
+    }
+
+    // A list of empty compounds is a byte per element in the file, but a whole tag in memory
+    #[test]
+    fn rejects_files_that_inflate_in_memory() {
+        let len = NbtLimits::FILE.max_size / 32;
+        let mut bytes = vec![10, 0, 0, 9, 0, 1, b'l', 10];
+        bytes.extend_from_slice(&(len as i32).to_be_bytes());
+        bytes.resize(bytes.len() + len, 0);
+        bytes.push(0);
+
+        let mut encoder = GzEncoder::new(vec![], flate2::Compression::fast());
+        encoder.write_all(&bytes).unwrap();
+        let bytes = encoder.finish().unwrap();
+        assert!(bytes.len() < len / 100);
+
+        assert!(matches!(
+            decompress_nbt(&bytes),
+            Err(NbtFileError::TooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn writes_and_reads_files() {
+        let path = std::env::temp_dir().join(format!("mars-{}-level.dat", std::process::id()));
+
+        write_nbt_file(&path, level(), Compression::Gzip).unwrap();
+        let (compound, compression) = read_nbt_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(compression, Compression::Gzip);
+        assert_eq!(compound.into_owned(), level());
+    }
+}
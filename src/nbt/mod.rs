@@ -1,8 +1,25 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    borrow::Cow,
+    fmt::{self, Display},
     io::{self, Write},
 };
 
+mod de;
+mod file;
+mod mutf8;
+mod reader;
+mod ser;
+pub mod snbt;
+
+pub use de::{from_bytes, from_compound, Deserializer};
+pub use file::{
+    compress_nbt, decompress_nbt, read_nbt_file, write_nbt_file, Compression, NbtFileError,
+};
+pub use reader::{NbtLimits, ReadNbtExt};
+pub use ser::{to_bytes, to_compound, Serializer};
+
 pub trait WriteNbtExt: Write {
     fn write_type(&mut self, value: NbtType) -> io::Result<()> {
         self.write_all(&[value as u8])
@@ -42,7 +59,7 @@ pub trait WriteNbtExt: Write {
 
     fn write_string(&mut self, value: Nbt<'_>) -> io::Result<()> {
         if let Nbt::String(value) = value {
-            self.write_len_prefixed_string(value)
+            self.write_len_prefixed_string(&value)
         } else {
             Err(io::Error::new(
                 io::ErrorKind::Other,
@@ -52,9 +69,16 @@ pub trait WriteNbtExt: Write {
     }
 
     fn write_len_prefixed_string(&mut self, value: &str) -> io::Result<()> {
-        let len = value.len() as u16;
+        let bytes = mutf8::encode(value);
+        let len = u16::try_from(bytes.len()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("String is {} bytes long, NBT allows 65535", bytes.len()),
+            )
+        })?;
+
         self.write_all(&len.to_be_bytes())?;
-        self.write_all(value.as_bytes())
+        self.write_all(&bytes)
     }
 
     fn write_f32(&mut self, value: Nbt<'_>) -> io::Result<()> {
@@ -81,21 +105,33 @@ pub trait WriteNbtExt: Write {
             Nbt::Long(_) => self.write_i64(value),
             Nbt::Float(_) => self.write_f32(value),
             Nbt::Double(_) => self.write_f64(value),
-            Nbt::ByteArray(_) => todo!(),
+            Nbt::ByteArray(_) => self.write_byte_array(value),
             Nbt::String(_) => self.write_string(value),
-            Nbt::List(_) => todo!(),
-            Nbt::Compound(c) => self.write_compound(c),
-            Nbt::IntArray(_) => todo!(),
+            Nbt::List(_) => self.write_list(value),
+            Nbt::Compound(c) => self.write_compound_payload(c),
+            Nbt::IntArray(_) => self.write_int_array(value),
             Nbt::LongArray(_) => self.write_long_array(value),
         }
     }
 
     fn write_compound(&mut self, value: NbtCompound<'_>) -> io::Result<()> {
+        self.write_root(value, NbtFormat::Named)
+    }
+
+    // Root compounds carry a type and, depending on the format, a name.
+    // Nested ones are just their payload.
+    fn write_root(&mut self, value: NbtCompound<'_>, format: NbtFormat) -> io::Result<()> {
         self.write_type(NbtType::Compound)?;
-        self.write_len_prefixed_string("")?;
+        if format == NbtFormat::Named {
+            self.write_len_prefixed_string("")?;
+        }
+        self.write_compound_payload(value)
+    }
+
+    fn write_compound_payload(&mut self, value: NbtCompound<'_>) -> io::Result<()> {
         for (key, value) in value.0 {
             self.write_type(value.get_type())?;
-            self.write_len_prefixed_string(key)?;
+            self.write_len_prefixed_string(&key)?;
             self.write_tag(value)?;
         }
         self.write_type(NbtType::End)?;
@@ -103,6 +139,54 @@ pub trait WriteNbtExt: Write {
         Ok(())
     }
 
+    fn write_list(&mut self, value: Nbt<'_>) -> io::Result<()> {
+        if let Nbt::List(value) = value {
+            // Empty lists have no element type
+            let element_type = value.first().map_or(NbtType::End, Nbt::get_type);
+            if value
+                .iter()
+                .any(|element| element.get_type() != element_type)
+            {
+                return Err(io::Error::other(
+                    "List elements must all have the same type",
+                ));
+            }
+
+            self.write_type(element_type)?;
+            self.write_i32(Nbt::Int(value.len() as i32))?;
+            for element in value {
+                self.write_tag(element)?;
+            }
+
+            Ok(())
+        } else {
+            Err(io::Error::other("Value is not List"))
+        }
+    }
+
+    fn write_byte_array(&mut self, value: Nbt<'_>) -> io::Result<()> {
+        if let Nbt::ByteArray(value) = value {
+            self.write_i32(Nbt::Int(value.len() as i32))?;
+            self.write_all(&value)
+        } else {
+            Err(io::Error::other("Value is not ByteArray"))
+        }
+    }
+
+    fn write_int_array(&mut self, value: Nbt<'_>) -> io::Result<()> {
+        if let Nbt::IntArray(value) = value {
+            self.write_i32(Nbt::Int(value.len() as i32))?;
+
+            for v in value {
+                self.write_i32(Nbt::Int(v))?;
+            }
+
+            Ok(())
+        } else {
+            Err(io::Error::other("Value is not IntArray"))
+        }
+    }
+
     fn write_long_array(&mut self, value: Nbt<'_>) -> io::Result<()> {
         if let Nbt::LongArray(value) = value {
             self.write_i32(Nbt::Int(value.len() as i32))?;
@@ -120,144 +204,150 @@ pub trait WriteNbtExt: Write {
 
 impl<W: Write + ?Sized> WriteNbtExt for W {}
 
-#[derive(Debug)]
-pub struct NbtCompound<'a>(HashMap<&'a str, Nbt<'a>>);
+// Keys and values either borrow from the caller or own their data, see `into_owned`.
+// Keys are written in the order they were first set, so packing is deterministic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NbtCompound<'a>(IndexMap<Cow<'a, str>, Nbt<'a>>);
 
 impl<'a> NbtCompound<'a> {
     pub fn default() -> Self {
-        NbtCompound(HashMap::new())
+        NbtCompound(IndexMap::new())
     }
 
-    fn add_element(&mut self, key: &'a str, value: Nbt<'a>) {
-        self.0.insert(key, value);
+    fn add_element(&mut self, key: impl Into<Cow<'a, str>>, value: Nbt<'a>) {
+        self.0.insert(key.into(), value);
     }
 
-    pub fn set_byte(&mut self, key: &'a str, value: u8) {
+    pub fn set_byte(&mut self, key: impl Into<Cow<'a, str>>, value: u8) {
         self.add_element(key, Nbt::Byte(value));
     }
 
-    pub fn set_short(&mut self, key: &'a str, value: i16) {
+    pub fn set_short(&mut self, key: impl Into<Cow<'a, str>>, value: i16) {
         self.add_element(key, Nbt::Short(value));
     }
 
-    pub fn set_int(&mut self, key: &'a str, value: i32) {
+    pub fn set_int(&mut self, key: impl Into<Cow<'a, str>>, value: i32) {
         self.add_element(key, Nbt::Int(value));
     }
 
-    pub fn set_long(&mut self, key: &'a str, value: i64) {
+    pub fn set_long(&mut self, key: impl Into<Cow<'a, str>>, value: i64) {
         self.add_element(key, Nbt::Long(value));
     }
 
-    pub fn set_float(&mut self, key: &'a str, value: f32) {
+    pub fn set_float(&mut self, key: impl Into<Cow<'a, str>>, value: f32) {
         self.add_element(key, Nbt::Float(value));
     }
 
-    pub fn set_double(&mut self, key: &'a str, value: f64) {
+    pub fn set_double(&mut self, key: impl Into<Cow<'a, str>>, value: f64) {
         self.add_element(key, Nbt::Double(value));
     }
 
-    pub fn set_byte_array(&mut self, key: &'a str, value: &'a [u8]) {
-        self.add_element(key, Nbt::ByteArray(value));
+    pub fn set_byte_array(
+        &mut self,
+        key: impl Into<Cow<'a, str>>,
+        value: impl Into<Cow<'a, [u8]>>,
+    ) {
+        self.add_element(key, Nbt::ByteArray(value.into()));
     }
 
-    pub fn set_string(&mut self, key: &'a str, value: &'a str) {
-        self.add_element(key, Nbt::String(value));
+    pub fn set_string(&mut self, key: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) {
+        self.add_element(key, Nbt::String(value.into()));
     }
 
-    pub fn set_list(&mut self, key: &'a str, value: Vec<Nbt<'a>>) {
+    pub fn set_list(&mut self, key: impl Into<Cow<'a, str>>, value: Vec<Nbt<'a>>) {
         self.add_element(key, Nbt::List(value));
     }
 
-    pub fn set_compound(&mut self, key: &'a str, value: NbtCompound<'a>) {
+    pub fn set_compound(&mut self, key: impl Into<Cow<'a, str>>, value: NbtCompound<'a>) {
         self.add_element(key, Nbt::Compound(value));
     }
 
-    pub fn set_int_array(&mut self, key: &'a str, value: Vec<i32>) {
+    pub fn set_int_array(&mut self, key: impl Into<Cow<'a, str>>, value: Vec<i32>) {
         self.add_element(key, Nbt::IntArray(value));
     }
 
-    pub fn set_long_array(&mut self, key: &'a str, value: Vec<i64>) {
+    pub fn set_long_array(&mut self, key: impl Into<Cow<'a, str>>, value: Vec<i64>) {
         self.add_element(key, Nbt::LongArray(value))
     }
 
-    pub fn get_byte(&self, key: &'a str) -> Option<u8> {
+    pub fn get_byte(&self, key: &str) -> Option<u8> {
         match self.0.get(key) {
             Some(Nbt::Byte(value)) => Some(*value),
             _ => None,
         }
     }
 
-    pub fn get_short(&self, key: &'a str) -> Option<i16> {
+    pub fn get_short(&self, key: &str) -> Option<i16> {
         match self.0.get(key) {
             Some(Nbt::Short(value)) => Some(*value),
             _ => None,
         }
     }
 
-    pub fn get_int(&self, key: &'a str) -> Option<i32> {
+    pub fn get_int(&self, key: &str) -> Option<i32> {
         match self.0.get(key) {
             Some(Nbt::Int(value)) => Some(*value),
             _ => None,
         }
     }
 
-    pub fn get_long(&self, key: &'a str) -> Option<i64> {
+    pub fn get_long(&self, key: &str) -> Option<i64> {
         match self.0.get(key) {
             Some(Nbt::Long(value)) => Some(*value),
             _ => None,
         }
     }
 
-    pub fn get_float(&self, key: &'a str) -> Option<f32> {
+    pub fn get_float(&self, key: &str) -> Option<f32> {
         match self.0.get(key) {
             Some(Nbt::Float(value)) => Some(*value),
             _ => None,
         }
     }
 
-    pub fn get_double(&self, key: &'a str) -> Option<f64> {
+    pub fn get_double(&self, key: &str) -> Option<f64> {
         match self.0.get(key) {
             Some(Nbt::Double(value)) => Some(*value),
             _ => None,
         }
     }
 
-    pub fn get_byte_array(&self, key: &'a str) -> Option<&'a [u8]> {
+    pub fn get_byte_array(&self, key: &str) -> Option<&[u8]> {
         match self.0.get(key) {
-            Some(Nbt::ByteArray(value)) => Some(*value),
+            Some(Nbt::ByteArray(value)) => Some(value),
             _ => None,
         }
     }
 
-    pub fn get_string(&self, key: &'a str) -> Option<&'a str> {
+    pub fn get_string(&self, key: &str) -> Option<&str> {
         match self.0.get(key) {
-            Some(Nbt::String(value)) => Some(*value),
+            Some(Nbt::String(value)) => Some(value),
             _ => None,
         }
     }
 
-    pub fn get_list(&self, key: &'a str) -> Option<&Vec<Nbt<'a>>> {
+    pub fn get_list(&self, key: &str) -> Option<&Vec<Nbt<'a>>> {
         match self.0.get(key) {
             Some(Nbt::List(value)) => Some(value),
             _ => None,
         }
     }
 
-    pub fn get_compound(&self, key: &'a str) -> Option<&NbtCompound<'a>> {
+    pub fn get_compound(&self, key: &str) -> Option<&NbtCompound<'a>> {
         match self.0.get(key) {
             Some(Nbt::Compound(value)) => Some(value),
             _ => None,
         }
     }
 
-    pub fn get_int_array(&self, key: &'a str) -> Option<&[i32]> {
+    pub fn get_int_array(&self, key: &str) -> Option<&[i32]> {
         match self.0.get(key) {
             Some(Nbt::IntArray(value)) => Some(value),
             _ => None,
         }
     }
 
-    pub fn get_long_array(&self, key: &'a str) -> Option<&[i64]> {
+    pub fn get_long_array(&self, key: &str) -> Option<&[i64]> {
         match self.0.get(key) {
             Some(Nbt::LongArray(value)) => Some(value),
             _ => None,
@@ -266,14 +356,37 @@ impl<'a> NbtCompound<'a> {
 }
 
 impl<'a> NbtCompound<'a> {
+    // Detaches the compound from whatever it borrowed, so it can be kept around
+    pub fn into_owned(self) -> NbtCompound<'static> {
+        NbtCompound(
+            self.0
+                .into_iter()
+                .map(|(key, value)| (Cow::Owned(key.into_owned()), value.into_owned()))
+                .collect(),
+        )
+    }
+
     pub fn pack(self) -> io::Result<Vec<u8>> {
+        self.pack_as(NbtFormat::Named)
+    }
+
+    pub fn pack_as(self, format: NbtFormat) -> io::Result<Vec<u8>> {
         let mut buffer = vec![];
-        buffer.write_compound(self)?;
+        buffer.write_root(self, format)?;
 
         Ok(buffer)
     }
 }
 
+// Files and packets before 1.20.2 give the root compound an (empty) name, later packets don't
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NbtFormat {
+    #[default]
+    Named,
+    Network,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NbtType {
     End = 0,
     Byte,
@@ -290,7 +403,30 @@ pub enum NbtType {
     LongArray,
 }
 
-#[derive(Debug)]
+impl NbtType {
+    pub fn from_id(id: u8) -> Option<Self> {
+        let value = match id {
+            0 => Self::End,
+            1 => Self::Byte,
+            2 => Self::Short,
+            3 => Self::Int,
+            4 => Self::Long,
+            5 => Self::Float,
+            6 => Self::Double,
+            7 => Self::ByteArray,
+            8 => Self::String,
+            9 => Self::List,
+            10 => Self::Compound,
+            11 => Self::IntArray,
+            12 => Self::LongArray,
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Nbt<'a> {
     Byte(u8),
     Short(i16),
@@ -298,8 +434,8 @@ pub enum Nbt<'a> {
     Long(i64),
     Float(f32),
     Double(f64),
-    ByteArray(&'a [u8]),
-    String(&'a str),
+    ByteArray(Cow<'a, [u8]>),
+    String(Cow<'a, str>),
     List(Vec<Nbt<'a>>),
     Compound(NbtCompound<'a>),
     IntArray(Vec<i32>),
@@ -307,6 +443,23 @@ pub enum Nbt<'a> {
 }
 
 impl<'a> Nbt<'a> {
+    pub fn into_owned(self) -> Nbt<'static> {
+        match self {
+            Nbt::Byte(value) => Nbt::Byte(value),
+            Nbt::Short(value) => Nbt::Short(value),
+            Nbt::Int(value) => Nbt::Int(value),
+            Nbt::Long(value) => Nbt::Long(value),
+            Nbt::Float(value) => Nbt::Float(value),
+            Nbt::Double(value) => Nbt::Double(value),
+            Nbt::ByteArray(value) => Nbt::ByteArray(Cow::Owned(value.into_owned())),
+            Nbt::String(value) => Nbt::String(Cow::Owned(value.into_owned())),
+            Nbt::List(value) => Nbt::List(value.into_iter().map(Nbt::into_owned).collect()),
+            Nbt::Compound(value) => Nbt::Compound(value.into_owned()),
+            Nbt::IntArray(value) => Nbt::IntArray(value),
+            Nbt::LongArray(value) => Nbt::LongArray(value),
+        }
+    }
+
     pub fn get_type(&self) -> NbtType {
         match self {
             Self::Byte(_) => NbtType::Byte,
@@ -324,3 +477,253 @@ impl<'a> Nbt<'a> {
         }
     }
 }
+
+// Plain sequences become lists, these make serde write the matching array tag instead.
+// The serializer recognizes them by their newtype name.
+const BYTE_ARRAY: &str = "__nbt_byte_array";
+const INT_ARRAY: &str = "__nbt_int_array";
+const LONG_ARRAY: &str = "__nbt_long_array";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "__nbt_byte_array")]
+pub struct ByteArray(pub Vec<u8>);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "__nbt_int_array")]
+pub struct IntArray(pub Vec<i32>);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "__nbt_long_array")]
+pub struct LongArray(pub Vec<i64>);
+
+#[derive(Debug)]
+pub struct Error(String);
+
+impl Error {
+    fn new(message: impl Into<String>) -> Self {
+        Error(message.into())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Error(message.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Error(message.to_string())
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One of every tag, strings and byte arrays borrowed from `source`
+    fn every_tag<'a>(source: &'a str, bytes: &'a [u8]) -> NbtCompound<'a> {
+        let mut nested = NbtCompound::default();
+        nested.set_string(&source[..4], &source[4..]);
+
+        let mut compound = NbtCompound::default();
+        compound.set_byte("byte", 0xfe);
+        compound.set_short("short", -2);
+        compound.set_int("int", 1 << 20);
+        compound.set_long("long", i64::MIN);
+        compound.set_float("float", 0.5);
+        compound.set_double("double", -1.25);
+        compound.set_byte_array("byte_array", bytes);
+        compound.set_string(source, source);
+        compound.set_list(
+            "list",
+            vec![Nbt::String(Cow::Borrowed(source)), Nbt::String("b".into())],
+        );
+        compound.set_compound("compound", nested);
+        compound.set_int_array("int_array", vec![1, -1]);
+        compound.set_long_array("long_array", vec![i64::MAX]);
+
+        compound
+    }
+
+    fn assert_owned(value: &Nbt<'_>) {
+        match value {
+            Nbt::ByteArray(value) => assert!(matches!(value, Cow::Owned(_))),
+            Nbt::String(value) => assert!(matches!(value, Cow::Owned(_))),
+            Nbt::List(value) => value.iter().for_each(assert_owned),
+            Nbt::Compound(value) => {
+                for (key, value) in &value.0 {
+                    assert!(matches!(key, Cow::Owned(_)), "{} is borrowed", key);
+                    assert_owned(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn into_owned_keeps_every_tag() {
+        let source = String::from("name_value");
+        let bytes = vec![1, 2, 3];
+
+        let borrowed = every_tag(&source, &bytes);
+        let owned: NbtCompound<'static> = borrowed.clone().into_owned();
+        drop(source);
+        drop(bytes);
+
+        assert_eq!(owned, every_tag("name_value", &[1, 2, 3]));
+        assert_eq!(owned.0.len(), 12);
+        assert_owned(&Nbt::Compound(owned));
+    }
+
+    #[test]
+    fn nbt_into_owned_keeps_nested_tags() {
+        let source = String::from("value");
+        let borrowed = Nbt::List(vec![Nbt::Compound(every_tag(&source, source.as_bytes()))]);
+
+        let owned: Nbt<'static> = borrowed.clone().into_owned();
+        assert_eq!(owned, borrowed);
+        assert_owned(&owned);
+    }
+
+    fn round_trip(compound: NbtCompound<'_>) -> NbtCompound<'static> {
+        let bytes = compound.pack().unwrap();
+
+        (&bytes[..]).read_nbt().unwrap()
+    }
+
+    #[test]
+    fn arrays_round_trip() {
+        let mut compound = NbtCompound::default();
+        compound.set_byte_array("bytes", vec![0, 0x7f, 0x80, 0xff]);
+        compound.set_int_array("ints", vec![i32::MIN, -1, 0, i32::MAX]);
+        compound.set_long_array("longs", vec![i64::MIN, -1, 0, i64::MAX]);
+        compound.set_byte_array("no_bytes", vec![]);
+        compound.set_int_array("no_ints", vec![]);
+        compound.set_long_array("no_longs", vec![]);
+
+        assert_eq!(round_trip(compound.clone()), compound);
+    }
+
+    #[test]
+    fn arrays_are_written_as_vanilla_does() {
+        let mut compound = NbtCompound::default();
+        compound.set_int_array("i", vec![1, -1]);
+
+        #[rustfmt::skip]
+        let expected = [
+            10, 0, 0,
+            11, 0, 1, b'i', 0, 0, 0, 2, 0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff,
+            0,
+        ];
+        assert_eq!(compound.pack().unwrap(), expected);
+    }
+
+    #[test]
+    fn nested_lists_round_trip() {
+        let mut element = NbtCompound::default();
+        element.set_list("empty", vec![]);
+        element.set_byte_array("bytes", vec![1, 2]);
+
+        let mut compound = NbtCompound::default();
+        compound.set_list(
+            "lists",
+            vec![
+                Nbt::List(vec![Nbt::Int(1), Nbt::Int(2)]),
+                Nbt::List(vec![]),
+                Nbt::List(vec![Nbt::List(vec![Nbt::String("deep".into())])]),
+            ],
+        );
+        compound.set_list(
+            "compounds",
+            vec![Nbt::Compound(element.clone()), Nbt::Compound(element)],
+        );
+        compound.set_list(
+            "arrays",
+            vec![Nbt::IntArray(vec![1]), Nbt::IntArray(vec![])],
+        );
+
+        assert_eq!(round_trip(compound.clone()), compound);
+    }
+
+    #[test]
+    fn empty_lists_have_end_type() {
+        let mut compound = NbtCompound::default();
+        compound.set_list("l", vec![]);
+
+        let expected = [10, 0, 0, 9, 0, 1, b'l', 0, 0, 0, 0, 0, 0];
+        assert_eq!(compound.pack().unwrap(), expected);
+    }
+
+    #[test]
+    fn rejects_mixed_lists() {
+        let mut compound = NbtCompound::default();
+        compound.set_list("l", vec![Nbt::Int(1), Nbt::Long(1)]);
+
+        assert!(compound.pack().is_err());
+    }
+
+    #[test]
+    fn network_roots_have_no_name() {
+        let mut compound = NbtCompound::default();
+        compound.set_byte("b", 1);
+
+        let expected = [10, 1, 0, 1, b'b', 1, 0];
+        assert_eq!(
+            compound.clone().pack_as(NbtFormat::Network).unwrap(),
+            expected
+        );
+        assert_eq!(compound.pack().unwrap()[3..], expected[1..]);
+
+        let empty = NbtCompound::default().pack_as(NbtFormat::Network).unwrap();
+        assert_eq!(empty, [10, 0]);
+    }
+
+    #[test]
+    fn network_roots_round_trip() {
+        let source = String::from("value");
+        let compound = every_tag(&source, source.as_bytes()).into_owned();
+
+        let bytes = compound.clone().pack_as(NbtFormat::Network).unwrap();
+        let mut reader = &bytes[..];
+        let read = reader
+            .read_nbt_as(NbtFormat::Network, NbtLimits::default())
+            .unwrap();
+        assert_eq!(read, compound);
+        assert!(reader.is_empty());
+
+        let empty = NbtCompound::default().pack_as(NbtFormat::Network).unwrap();
+        let read = (&empty[..])
+            .read_nbt_as(NbtFormat::Network, NbtLimits::default())
+            .unwrap();
+        assert_eq!(read, NbtCompound::default());
+    }
+
+    // With the wrong format the name's length is taken for a tag or the other way around
+    #[test]
+    fn formats_are_not_interchangeable() {
+        let mut compound = NbtCompound::default();
+        compound.set_int("i", 1);
+
+        let named = compound.clone().pack().unwrap();
+        let read = (&named[..]).read_nbt_as(NbtFormat::Network, NbtLimits::default());
+        assert!(read.map_or(true, |read| read != compound));
+
+        let network = compound.pack_as(NbtFormat::Network).unwrap();
+        assert!((&network[..]).read_nbt().is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use std::{borrow::Cow, io};
+
+// Java's Modified UTF-8: NUL takes two bytes and characters outside the BMP are written as
+// two 3-byte surrogates instead of one 4-byte sequence. Everything else matches UTF-8.
+pub fn encode(value: &str) -> Cow<'_, [u8]> {
+    if !value.bytes().any(|byte| byte == 0 || byte >= 0xf0) {
+        return Cow::Borrowed(value.as_bytes());
+    }
+
+    let mut bytes = Vec::with_capacity(value.len() + 2);
+    for c in value.chars() {
+        match c as u32 {
+            0 => bytes.extend_from_slice(&[0xc0, 0x80]),
+            0x10000.. => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    encode_unit(&mut bytes, *unit);
+                }
+            }
+            _ => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+
+    Cow::Owned(bytes)
+}
+
+fn encode_unit(bytes: &mut Vec<u8>, unit: u16) {
+    bytes.extend_from_slice(&[
+        0xe0 | (unit >> 12) as u8,
+        0x80 | (unit >> 6 & 0x3f) as u8,
+        0x80 | (unit & 0x3f) as u8,
+    ]);
+}
+
+// A raw 0x00 never comes out of Java's encoder, NUL is always the two-byte form
+pub fn decode(bytes: Vec<u8>) -> io::Result<String> {
+    if bytes.is_ascii() && !bytes.contains(&0) {
+        return Ok(String::from_utf8(bytes).unwrap());
+    }
+
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "String is not valid MUTF-8");
+    let continuation = |byte: Option<&u8>| match byte {
+        Some(byte) if byte & 0xc0 == 0x80 => Ok((byte & 0x3f) as u16),
+        _ => Err(invalid()),
+    };
+
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter();
+    while let Some(&byte) = iter.next() {
+        let unit = match byte {
+            0x01..=0x7f => byte as u16,
+            0xc0..=0xdf => (byte as u16 & 0x1f) << 6 | continuation(iter.next())?,
+            0xe0..=0xef => {
+                (byte as u16 & 0x0f) << 12
+                    | continuation(iter.next())? << 6
+                    | continuation(iter.next())?
+            }
+            _ => return Err(invalid()),
+        };
+        units.push(unit);
+    }
+
+    // Lone surrogates are fine in Java but can't be held by a Rust string
+    String::from_utf16(&units).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(value: &str, encoded: &[u8]) {
+        assert_eq!(encode(value), encoded);
+        assert_eq!(decode(encoded.to_vec()).unwrap(), value);
+    }
+
+    #[test]
+    fn plain_text_is_borrowed() {
+        assert!(matches!(encode("minecraft:stone"), Cow::Borrowed(_)));
+        assert!(matches!(encode("Grüße, 世界"), Cow::Borrowed(_)));
+        round_trip("Grüße, 世界", "Grüße, 世界".as_bytes());
+    }
+
+    #[test]
+    fn nul_takes_two_bytes() {
+        round_trip("\0", &[0xc0, 0x80]);
+        round_trip("a\0b", &[b'a', 0xc0, 0x80, b'b']);
+    }
+
+    #[test]
+    fn supplementary_characters_are_surrogate_pairs() {
+        // U+1F600 is the surrogate pair D83D DE00
+        round_trip("😀", &[0xed, 0xa0, 0xbd, 0xed, 0xb8, 0x80]);
+        round_trip(
+            "a😀\0",
+            &[b'a', 0xed, 0xa0, 0xbd, 0xed, 0xb8, 0x80, 0xc0, 0x80],
+        );
+    }
+
+    #[test]
+    fn rejects_raw_nul() {
+        assert!(decode(vec![0]).is_err());
+        assert!(decode(vec![b'a', 0, b'b']).is_err());
+        assert!(decode("é\0".as_bytes().to_vec()).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_sequences() {
+        // Plain UTF-8 four byte sequence
+        assert!(decode("😀".as_bytes().to_vec()).is_err());
+        // Continuation byte without a lead byte
+        assert!(decode(vec![0x80]).is_err());
+        // Truncated sequences
+        assert!(decode(vec![0xc3]).is_err());
+        assert!(decode(vec![0xe4, 0xb8]).is_err());
+        // Lead byte followed by something that isn't a continuation
+        assert!(decode(vec![0xc3, b'a']).is_err());
+        // Lone surrogates
+        assert!(decode(vec![0xed, 0xa0, 0xbd]).is_err());
+        assert!(decode(vec![0xed, 0xb8, 0x80, 0xed, 0xa0, 0xbd]).is_err());
+    }
+}
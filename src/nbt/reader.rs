@@ -0,0 +1,305 @@
+use indexmap::IndexMap;
+use std::{
+    borrow::Cow,
+    io::{self, Read},
+    mem::size_of,
+};
+
+use super::{mutf8, Nbt, NbtCompound, NbtFormat, NbtType};
+
+// `max_size` bounds the memory the read tags take, not the bytes they're read from. An empty
+// compound is one byte on the wire but a whole `Nbt` once read.
+#[derive(Debug, Clone, Copy)]
+pub struct NbtLimits {
+    pub max_depth: usize,
+    pub max_size: usize,
+}
+
+impl NbtLimits {
+    // Same limits vanilla uses for NBT coming from clients
+    pub const NETWORK: Self = NbtLimits {
+        max_depth: 512,
+        max_size: 2 * 1024 * 1024,
+    };
+
+    pub const FILE: Self = NbtLimits {
+        max_depth: 512,
+        max_size: 100 * 1024 * 1024,
+    };
+}
+
+impl Default for NbtLimits {
+    fn default() -> Self {
+        Self::NETWORK
+    }
+}
+
+pub trait ReadNbtExt: Read {
+    fn read_nbt(&mut self) -> io::Result<NbtCompound<'static>> {
+        self.read_nbt_with_limits(NbtLimits::default())
+    }
+
+    fn read_nbt_with_limits(&mut self, limits: NbtLimits) -> io::Result<NbtCompound<'static>> {
+        self.read_nbt_as(NbtFormat::Named, limits)
+    }
+
+    // The root compound's name is read and dropped, it's always empty in practice
+    fn read_nbt_as(
+        &mut self,
+        format: NbtFormat,
+        limits: NbtLimits,
+    ) -> io::Result<NbtCompound<'static>> {
+        let mut reader = NbtReader {
+            reader: self,
+            limits,
+            depth: 0,
+            size: 0,
+        };
+
+        if reader.read_type()? != NbtType::Compound {
+            return Err(invalid_data("Root tag is not a compound"));
+        }
+        if format == NbtFormat::Named {
+            reader.read_string()?;
+        }
+
+        reader.read_compound()
+    }
+}
+
+impl<R: Read + ?Sized> ReadNbtExt for R {}
+
+struct NbtReader<'r, R: Read + ?Sized> {
+    reader: &'r mut R,
+    limits: NbtLimits,
+    depth: usize,
+    size: usize,
+}
+
+// Every tag takes an `Nbt`, wherever it's stored
+const TAG_SIZE: usize = size_of::<Nbt>();
+
+// Compound entries keep their key and hash next to the value, plus a slot in the index table
+const ENTRY_SIZE: usize = size_of::<Cow<str>>() + 2 * size_of::<usize>();
+
+impl<R: Read + ?Sized> NbtReader<'_, R> {
+    // Like vanilla's NbtAccounter, memory is paid for before it's allocated
+    fn account(&mut self, bytes: usize) -> io::Result<()> {
+        self.size = self.size.saturating_add(bytes);
+        if self.size > self.limits.max_size {
+            return Err(io::Error::new(
+                io::ErrorKind::OutOfMemory,
+                format!("NBT takes more than {} bytes", self.limits.max_size),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut buffer = [0; N];
+        self.reader.read_exact(&mut buffer)?;
+
+        Ok(buffer)
+    }
+
+    fn read_type(&mut self) -> io::Result<NbtType> {
+        let [id] = self.read_bytes()?;
+
+        NbtType::from_id(id).ok_or_else(|| invalid_data(&format!("Unknown tag type {}", id)))
+    }
+
+    // Arrays pay for their elements here, list elements pay for themselves as they're read
+    fn read_len(&mut self, element_size: usize) -> io::Result<usize> {
+        let len = i32::from_be_bytes(self.read_bytes()?);
+        let len = usize::try_from(len).map_err(|_| invalid_data("Negative length"))?;
+        self.account(len.saturating_mul(element_size))?;
+
+        Ok(len)
+    }
+
+    fn read_string(&mut self) -> io::Result<String> {
+        let len = u16::from_be_bytes(self.read_bytes()?) as usize;
+        self.account(len)?;
+
+        let mut buffer = vec![0; len];
+        self.reader.read_exact(&mut buffer)?;
+
+        mutf8::decode(buffer)
+    }
+
+    fn enter(&mut self) -> io::Result<()> {
+        self.depth += 1;
+        if self.depth > self.limits.max_depth {
+            return Err(invalid_data(&format!(
+                "NBT is nested deeper than {} levels",
+                self.limits.max_depth
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn read_compound(&mut self) -> io::Result<NbtCompound<'static>> {
+        self.enter()?;
+
+        let mut compound = IndexMap::new();
+        loop {
+            let tag_type = self.read_type()?;
+            if tag_type == NbtType::End {
+                break;
+            }
+
+            self.account(ENTRY_SIZE)?;
+            let key = self.read_string()?;
+            let value = self.read_payload(tag_type)?;
+            compound.insert(Cow::Owned(key), value);
+        }
+
+        self.depth -= 1;
+
+        Ok(NbtCompound(compound))
+    }
+
+    fn read_list(&mut self) -> io::Result<Vec<Nbt<'static>>> {
+        self.enter()?;
+
+        let element_type = self.read_type()?;
+        let len = self.read_len(0)?;
+        if element_type == NbtType::End && len > 0 {
+            return Err(invalid_data("List of End tags can't have elements"));
+        }
+
+        let mut list = Vec::with_capacity(len.min(1024));
+        for _ in 0..len {
+            list.push(self.read_payload(element_type)?);
+        }
+
+        self.depth -= 1;
+
+        Ok(list)
+    }
+
+    fn read_payload(&mut self, tag_type: NbtType) -> io::Result<Nbt<'static>> {
+        self.account(TAG_SIZE)?;
+
+        let value = match tag_type {
+            NbtType::End => return Err(invalid_data("Unexpected End tag")),
+            NbtType::Byte => Nbt::Byte(u8::from_be_bytes(self.read_bytes()?)),
+            NbtType::Short => Nbt::Short(i16::from_be_bytes(self.read_bytes()?)),
+            NbtType::Int => Nbt::Int(i32::from_be_bytes(self.read_bytes()?)),
+            NbtType::Long => Nbt::Long(i64::from_be_bytes(self.read_bytes()?)),
+            NbtType::Float => Nbt::Float(f32::from_be_bytes(self.read_bytes()?)),
+            NbtType::Double => Nbt::Double(f64::from_be_bytes(self.read_bytes()?)),
+            NbtType::ByteArray => {
+                let len = self.read_len(1)?;
+                let mut value = vec![0; len];
+                self.reader.read_exact(&mut value)?;
+
+                Nbt::ByteArray(Cow::Owned(value))
+            }
+            NbtType::String => Nbt::String(Cow::Owned(self.read_string()?)),
+            NbtType::List => Nbt::List(self.read_list()?),
+            NbtType::Compound => Nbt::Compound(self.read_compound()?),
+            NbtType::IntArray => {
+                let len = self.read_len(4)?;
+                let mut value = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let mut buffer = [0; 4];
+                    self.reader.read_exact(&mut buffer)?;
+                    value.push(i32::from_be_bytes(buffer));
+                }
+
+                Nbt::IntArray(value)
+            }
+            NbtType::LongArray => {
+                let len = self.read_len(8)?;
+                let mut value = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let mut buffer = [0; 8];
+                    self.reader.read_exact(&mut buffer)?;
+                    value.push(i64::from_be_bytes(buffer));
+                }
+
+                Nbt::LongArray(value)
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nbt::NbtCompound;
+
+    fn limits(max_size: usize) -> NbtLimits {
+        NbtLimits {
+            max_depth: 512,
+            max_size,
+        }
+    }
+
+    // A root holding the list `l` with `len` elements of `element_type`, each `element` long
+    fn list(element_type: NbtType, len: usize, element: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![10, 0, 0, 9, 0, 1, b'l', element_type as u8];
+        bytes.extend_from_slice(&(len as i32).to_be_bytes());
+        for _ in 0..len {
+            bytes.extend_from_slice(element);
+        }
+        bytes.push(0);
+        bytes
+    }
+
+    fn read(bytes: &[u8], max_size: usize) -> io::Result<NbtCompound<'static>> {
+        (&bytes[..]).read_nbt_with_limits(limits(max_size))
+    }
+
+    #[test]
+    fn empty_compounds_cost_a_tag_each() {
+        // A megabyte of input, but every element is a whole `Nbt` once read
+        let len = 1024 * 1024;
+        let bytes = list(NbtType::Compound, len, &[0]);
+        assert!(bytes.len() < NbtLimits::NETWORK.max_size);
+
+        let error = (&bytes[..]).read_nbt().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::OutOfMemory);
+    }
+
+    // The key, the entry holding it and the list itself, then one tag per element
+    #[test]
+    fn list_elements_are_paid_for_once() {
+        let cost = 1 + ENTRY_SIZE + TAG_SIZE + 1000 * TAG_SIZE;
+        let bytes = list(NbtType::Int, 1000, &[0, 0, 0, 7]);
+
+        let compound = read(&bytes, cost).unwrap();
+        assert_eq!(compound.get_list("l").unwrap().len(), 1000);
+        assert!(read(&bytes, cost - 1).is_err());
+    }
+
+    #[test]
+    fn arrays_are_paid_for_before_reading() {
+        // The claimed length alone is over the limit, nothing after it has to exist
+        let mut bytes = vec![10, 0, 0, 11, 0, 1, b'a'];
+        bytes.extend_from_slice(&(i32::MAX).to_be_bytes());
+
+        let error = read(&bytes, NbtLimits::FILE.max_size).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::OutOfMemory);
+    }
+
+    #[test]
+    fn strings_cost_their_length() {
+        let mut bytes = vec![10, 0, 0, 8, 0, 1, b's', 0, 100];
+        bytes.extend_from_slice(&[b'x'; 100]);
+        bytes.push(0);
+
+        let cost = 1 + ENTRY_SIZE + TAG_SIZE + 100;
+        assert!(read(&bytes, cost).is_ok());
+        assert!(read(&bytes, cost - 1).is_err());
+    }
+}
@@ -0,0 +1,460 @@
+use serde::{ser, Serialize};
+use std::{borrow::Cow, io};
+
+use super::{Error, Nbt, NbtCompound, NbtType, BYTE_ARRAY, INT_ARRAY, LONG_ARRAY};
+
+// The root has to serialize to a compound, like a struct or a map
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> io::Result<Vec<u8>> {
+    to_compound(value)?.pack()
+}
+
+pub fn to_compound<T: Serialize + ?Sized>(value: &T) -> io::Result<NbtCompound<'static>> {
+    match value.serialize(Serializer)? {
+        Some(Nbt::Compound(compound)) => Ok(compound),
+        _ => Err(Error::new("Root value is not a compound").into()),
+    }
+}
+
+// `None` and unit values serialize to nothing, so struct fields holding them are skipped
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Option<Nbt<'static>>;
+    type Error = Error;
+
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeVariant<SerializeList>;
+    type SerializeMap = SerializeCompound;
+    type SerializeStruct = SerializeCompound;
+    type SerializeStructVariant = SerializeVariant<SerializeCompound>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Byte(v as u8)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Byte(v as u8)))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Short(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Int(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Long(v)))
+    }
+
+    // NBT has no unsigned tags, unsigned values keep their bits in the signed tag of the same size
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Byte(v)))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Short(v as i16)))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Int(v as i32)))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Long(v as i64)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Float(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Double(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::String(Cow::Owned(v.to_string()))))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::String(Cow::Owned(v.to_owned()))))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::ByteArray(Cow::Owned(v.to_vec()))))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Error> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Error> {
+        Ok(None)
+    }
+
+    // An empty compound would read back as a map, so there is nothing a unit struct could be
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Error> {
+        Err(Error::new(format!("Unit struct {} has no NBT form", name)))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Error> {
+        let array_type = match name {
+            BYTE_ARRAY => NbtType::ByteArray,
+            INT_ARRAY => NbtType::IntArray,
+            LONG_ARRAY => NbtType::LongArray,
+            _ => return value.serialize(self),
+        };
+
+        match value.serialize(self)? {
+            Some(Nbt::List(elements)) => to_array(array_type, elements).map(Some),
+            _ => Err(Error::new("Array wrappers have to hold a sequence")),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Error> {
+        // Without a value the variant's compound would be empty and lose the variant's name
+        let value = value
+            .serialize(self)?
+            .ok_or_else(|| Error::new(format!("Variant {} holds an empty value", variant)))?;
+
+        Ok(wrap_variant(variant, value))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList, Error> {
+        Ok(SerializeList(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeList, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeList>, Error> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeCompound, Error> {
+        Ok(SerializeCompound {
+            compound: NbtCompound::default(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeCompound, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeCompound>, Error> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+fn to_array(array_type: NbtType, elements: Vec<Nbt<'static>>) -> Result<Nbt<'static>, Error> {
+    let mismatch = || Error::new(format!("Elements don't fit in a {:?}", array_type));
+
+    let array = match array_type {
+        NbtType::ByteArray => Nbt::ByteArray(Cow::Owned(
+            elements
+                .into_iter()
+                .map(|element| match element {
+                    Nbt::Byte(value) => Ok(value),
+                    _ => Err(mismatch()),
+                })
+                .collect::<Result<_, _>>()?,
+        )),
+        NbtType::IntArray => Nbt::IntArray(
+            elements
+                .into_iter()
+                .map(|element| match element {
+                    Nbt::Int(value) => Ok(value),
+                    _ => Err(mismatch()),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        _ => Nbt::LongArray(
+            elements
+                .into_iter()
+                .map(|element| match element {
+                    Nbt::Long(value) => Ok(value),
+                    _ => Err(mismatch()),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+    };
+
+    Ok(array)
+}
+
+pub struct SerializeList(Vec<Nbt<'static>>);
+
+impl SerializeList {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let value = value
+            .serialize(Serializer)?
+            .ok_or_else(|| Error::new("Lists can't hold empty values"))?;
+
+        // The writer would catch this too, but only once the whole value was built
+        if let Some(first) = self.0.first() {
+            if first.get_type() != value.get_type() {
+                return Err(Error::new("List elements must all have the same type"));
+            }
+        }
+        self.0.push(value);
+
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = Option<Nbt<'static>>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::List(self.0)))
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = Option<Nbt<'static>>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::List(self.0)))
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = Option<Nbt<'static>>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::List(self.0)))
+    }
+}
+
+pub struct SerializeCompound {
+    compound: NbtCompound<'static>,
+    key: Option<String>,
+}
+
+impl SerializeCompound {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), Error> {
+        if let Some(value) = value.serialize(Serializer)? {
+            self.compound.add_element(key, value);
+        }
+
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for SerializeCompound {
+    type Ok = Option<Nbt<'static>>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        match key.serialize(Serializer)? {
+            Some(Nbt::String(key)) => {
+                self.key = Some(key.into_owned());
+                Ok(())
+            }
+            _ => Err(Error::new("Compound keys must be strings")),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::new("Value serialized before its key"))?;
+
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Compound(self.compound)))
+    }
+}
+
+impl ser::SerializeStruct for SerializeCompound {
+    type Ok = Option<Nbt<'static>>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Some(Nbt::Compound(self.compound)))
+    }
+}
+
+// Variants with data are wrapped in a compound keyed by the variant's name
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+fn wrap_variant(variant: &'static str, value: Nbt<'static>) -> Option<Nbt<'static>> {
+    let mut compound = NbtCompound::default();
+    compound.add_element(variant, value);
+
+    Some(Nbt::Compound(compound))
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeList> {
+    type Ok = Option<Nbt<'static>>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(wrap_variant(self.variant, Nbt::List(self.inner.0)))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeCompound> {
+    type Ok = Option<Nbt<'static>>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.inner.insert(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(wrap_variant(
+            self.variant,
+            Nbt::Compound(self.inner.compound),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Marker;
+
+    #[derive(Serialize)]
+    enum Slot {
+        Item(Option<i32>),
+    }
+
+    #[derive(Serialize)]
+    struct Holder<T> {
+        value: T,
+    }
+
+    #[test]
+    fn unit_structs_are_rejected() {
+        assert!(to_bytes(&Marker).is_err());
+        assert!(to_bytes(&Holder { value: Marker }).is_err());
+    }
+
+    #[test]
+    fn empty_newtype_variants_are_rejected() {
+        assert!(to_bytes(&Holder {
+            value: Slot::Item(None)
+        })
+        .is_err());
+
+        let compound = to_compound(&Holder {
+            value: Slot::Item(Some(3)),
+        })
+        .unwrap();
+        assert_eq!(
+            compound.get_compound("value").unwrap().get_int("Item"),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn wrappers_need_matching_elements() {
+        assert!(to_bytes(&Holder {
+            value: crate::nbt::IntArray(vec![1, 2])
+        })
+        .is_ok());
+
+        #[derive(Serialize)]
+        #[serde(rename = "__nbt_int_array")]
+        struct Longs(Vec<i64>);
+        assert!(to_bytes(&Holder {
+            value: Longs(vec![1])
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,594 @@
+use indexmap::IndexMap;
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+    io,
+};
+
+use super::{Nbt, NbtCompound, NbtLimits, NbtType};
+
+// Stringified NBT, the text format used by commands and `/data get`
+
+pub fn parse(input: &str) -> io::Result<Nbt<'static>> {
+    let mut parser = Parser {
+        input,
+        position: 0,
+        depth: 0,
+        max_depth: NbtLimits::default().max_depth,
+    };
+
+    let value = parser.read_value()?;
+    parser.skip_whitespace();
+    if parser.position != input.len() {
+        return Err(parser.error("Trailing data"));
+    }
+
+    Ok(value)
+}
+
+pub fn parse_compound(input: &str) -> io::Result<NbtCompound<'static>> {
+    match parse(input)? {
+        Nbt::Compound(compound) => Ok(compound),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "SNBT is not a compound",
+        )),
+    }
+}
+
+struct Parser<'i> {
+    input: &'i str,
+    position: usize,
+    // Compounds and lists parse recursively, so deep input would overflow the stack
+    depth: usize,
+    max_depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} at position {}", message, self.position),
+        )
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> io::Result<()> {
+        self.skip_whitespace();
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("Expected '{}'", expected)));
+        }
+        self.position += 1;
+
+        Ok(())
+    }
+
+    // Skips the separator and tells whether another element follows
+    fn next_element(&mut self, end: char) -> io::Result<bool> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(',') => {
+                self.position += 1;
+                Ok(true)
+            }
+            Some(c) if c == end => Ok(false),
+            _ => Err(self.error(&format!("Expected ',' or '{}'", end))),
+        }
+    }
+
+    fn enter(&mut self) -> io::Result<()> {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            return Err(self.error(&format!(
+                "SNBT is nested deeper than {} levels",
+                self.max_depth
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn read_value(&mut self) -> io::Result<Nbt<'static>> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => Ok(Nbt::Compound(self.read_compound()?)),
+            Some('[') => self.read_list_or_array(),
+            Some('"' | '\'') => Ok(Nbt::String(Cow::Owned(self.read_quoted_string()?))),
+            Some(_) => {
+                let word = self.read_unquoted_string();
+                if word.is_empty() {
+                    return Err(self.error("Expected a value"));
+                }
+
+                Ok(parse_unquoted(word))
+            }
+            None => Err(self.error("Expected a value")),
+        }
+    }
+
+    fn read_key(&mut self) -> io::Result<String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"' | '\'') => self.read_quoted_string(),
+            _ => {
+                let key = self.read_unquoted_string();
+                if key.is_empty() {
+                    return Err(self.error("Expected a key"));
+                }
+
+                Ok(key.to_owned())
+            }
+        }
+    }
+
+    fn read_unquoted_string(&mut self) -> &str {
+        let start = self.position;
+        while let Some(c) = self.peek().filter(|c| is_unquoted_char(*c)) {
+            self.position += c.len_utf8();
+        }
+
+        &self.input[start..self.position]
+    }
+
+    fn read_quoted_string(&mut self) -> io::Result<String> {
+        let Some(quote) = self.peek() else {
+            return Err(self.error("Expected a string"));
+        };
+        self.position += 1;
+
+        let mut value = String::new();
+        let mut escaped = false;
+        while let Some(c) = self.peek() {
+            self.position += c.len_utf8();
+
+            if escaped {
+                if c != quote && c != '\\' {
+                    return Err(self.error(&format!("Invalid escape '\\{}'", c)));
+                }
+                value.push(c);
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                return Ok(value);
+            } else {
+                value.push(c);
+            }
+        }
+
+        Err(self.error("Unterminated string"))
+    }
+
+    fn read_compound(&mut self) -> io::Result<NbtCompound<'static>> {
+        self.expect('{')?;
+        self.enter()?;
+
+        let mut compound = IndexMap::new();
+        self.skip_whitespace();
+        if self.peek() != Some('}') {
+            loop {
+                let key = self.read_key()?;
+                self.expect(':')?;
+                let value = self.read_value()?;
+                compound.insert(Cow::Owned(key), value);
+
+                if !self.next_element('}')? {
+                    break;
+                }
+            }
+        }
+        self.expect('}')?;
+        self.depth -= 1;
+
+        Ok(NbtCompound(compound))
+    }
+
+    fn read_list_or_array(&mut self) -> io::Result<Nbt<'static>> {
+        self.expect('[')?;
+        self.enter()?;
+
+        // Typed arrays look like [B; 1b, 2b], anything else is a list
+        let rest = &self.input[self.position..];
+        let array_type = match rest.get(..2) {
+            Some("B;") => Some(NbtType::ByteArray),
+            Some("I;") => Some(NbtType::IntArray),
+            Some("L;") => Some(NbtType::LongArray),
+            _ => None,
+        };
+
+        let mut elements: Vec<Nbt<'static>> = vec![];
+        if array_type.is_some() {
+            self.position += 2;
+        }
+
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            loop {
+                let element = self.read_value()?;
+                if let Some(first) = elements.first() {
+                    if first.get_type() != element.get_type() {
+                        return Err(self.error("List elements must all have the same type"));
+                    }
+                }
+                elements.push(element);
+
+                if !self.next_element(']')? {
+                    break;
+                }
+            }
+        }
+        self.expect(']')?;
+        self.depth -= 1;
+
+        let value = match array_type {
+            None => Nbt::List(elements),
+            Some(NbtType::ByteArray) => Nbt::ByteArray(Cow::Owned(
+                elements
+                    .into_iter()
+                    .map(|element| match element {
+                        Nbt::Byte(value) => Ok(value),
+                        _ => Err(self.error("Byte arrays can only hold bytes")),
+                    })
+                    .collect::<io::Result<_>>()?,
+            )),
+            Some(NbtType::IntArray) => Nbt::IntArray(
+                elements
+                    .into_iter()
+                    .map(|element| match element {
+                        Nbt::Int(value) => Ok(value),
+                        _ => Err(self.error("Int arrays can only hold ints")),
+                    })
+                    .collect::<io::Result<_>>()?,
+            ),
+            Some(_) => Nbt::LongArray(
+                elements
+                    .into_iter()
+                    .map(|element| match element {
+                        Nbt::Long(value) => Ok(value),
+                        _ => Err(self.error("Long arrays can only hold longs")),
+                    })
+                    .collect::<io::Result<_>>()?,
+            ),
+        };
+
+        Ok(value)
+    }
+}
+
+fn is_unquoted_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+// Like vanilla, words that don't fit a number type are plain strings
+fn parse_unquoted(word: &str) -> Nbt<'static> {
+    let (number, suffix) = match word.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&word[..i], Some(c.to_ascii_lowercase())),
+        _ => (word, None),
+    };
+    let is_integer = is_integer(number);
+    let is_decimal = is_decimal(number);
+
+    let value = match suffix {
+        Some('b') if is_integer => number.parse::<i8>().ok().map(|v| Nbt::Byte(v as u8)),
+        Some('s') if is_integer => number.parse().ok().map(Nbt::Short),
+        Some('l') if is_integer => number.parse().ok().map(Nbt::Long),
+        Some('f') if is_decimal => number.parse().ok().map(Nbt::Float),
+        Some('d') if is_decimal => number.parse().ok().map(Nbt::Double),
+        None if is_integer => number.parse().ok().map(Nbt::Int),
+        // Without a suffix only the dot tells a double from an int
+        None if is_decimal && number.contains('.') => number.parse().ok().map(Nbt::Double),
+        _ => None,
+    };
+
+    value.unwrap_or_else(|| match word {
+        "true" => Nbt::Byte(1),
+        "false" => Nbt::Byte(0),
+        _ => Nbt::String(Cow::Owned(word.to_owned())),
+    })
+}
+
+fn is_integer(number: &str) -> bool {
+    let digits = number.strip_prefix(['-', '+']).unwrap_or(number);
+
+    !digits.is_empty()
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'))
+}
+
+fn is_decimal(number: &str) -> bool {
+    let number = number.strip_prefix(['-', '+']).unwrap_or(number);
+    let (mantissa, exponent) = match number.find(['e', 'E']) {
+        Some(i) => (&number[..i], Some(&number[i + 1..])),
+        None => (number, None),
+    };
+
+    let mut parts = mantissa.splitn(2, '.');
+    let whole = parts.next().unwrap_or_default();
+    let fraction = parts.next();
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+
+    let mantissa_ok = match fraction {
+        Some(fraction) => {
+            digits(whole) && digits(fraction) && !(whole.is_empty() && fraction.is_empty())
+        }
+        None => !whole.is_empty() && digits(whole),
+    };
+    let exponent_ok = exponent.is_none_or(|exponent| {
+        let exponent = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+        !exponent.is_empty() && digits(exponent)
+    });
+
+    mantissa_ok && exponent_ok
+}
+
+fn write_string(f: &mut impl Write, value: &str) -> fmt::Result {
+    // Vanilla switches to single quotes when that avoids escaping
+    let quote = if value.contains('"') && !value.contains('\'') {
+        '\''
+    } else {
+        '"'
+    };
+
+    f.write_char(quote)?;
+    for c in value.chars() {
+        if c == quote || c == '\\' {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char(quote)
+}
+
+fn write_key(f: &mut impl Write, key: &str) -> fmt::Result {
+    if !key.is_empty() && key.chars().all(is_unquoted_char) {
+        f.write_str(key)
+    } else {
+        write_string(f, key)
+    }
+}
+
+fn write_array<T: fmt::Display>(
+    f: &mut impl Write,
+    prefix: &str,
+    suffix: &str,
+    values: impl Iterator<Item = T>,
+) -> fmt::Result {
+    write!(f, "[{};", prefix)?;
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            f.write_char(',')?;
+        }
+        write!(f, "{}{}", value, suffix)?;
+    }
+    f.write_char(']')
+}
+
+// `indent` is None for the compact form, otherwise the current nesting level
+fn write_value(f: &mut impl Write, value: &Nbt<'_>, indent: Option<usize>) -> fmt::Result {
+    match value {
+        Nbt::Byte(value) => write!(f, "{}b", *value as i8),
+        Nbt::Short(value) => write!(f, "{}s", value),
+        Nbt::Int(value) => write!(f, "{}", value),
+        Nbt::Long(value) => write!(f, "{}L", value),
+        Nbt::Float(value) => write!(f, "{:?}f", value),
+        Nbt::Double(value) => write!(f, "{:?}d", value),
+        Nbt::ByteArray(value) => write_array(f, "B", "B", value.iter().map(|v| *v as i8)),
+        Nbt::String(value) => write_string(f, value),
+        Nbt::List(values) => {
+            // Only lists of lists and compounds are worth spreading over lines
+            let nested = indent.filter(|_| {
+                values
+                    .first()
+                    .is_some_and(|v| matches!(v, Nbt::List(_) | Nbt::Compound(_)))
+            });
+
+            f.write_char('[')?;
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    f.write_char(',')?;
+                }
+                if let Some(level) = nested {
+                    write_newline(f, level + 1)?;
+                } else if i > 0 && indent.is_some() {
+                    f.write_char(' ')?;
+                }
+                write_value(f, value, nested.map(|level| level + 1).or(indent))?;
+            }
+            if let Some(level) = nested {
+                write_newline(f, level)?;
+            }
+            f.write_char(']')
+        }
+        Nbt::Compound(compound) => write_compound(f, compound, indent),
+        Nbt::IntArray(value) => write_array(f, "I", "", value.iter()),
+        Nbt::LongArray(value) => write_array(f, "L", "L", value.iter()),
+    }
+}
+
+fn write_newline(f: &mut impl Write, level: usize) -> fmt::Result {
+    f.write_char('\n')?;
+    for _ in 0..level {
+        f.write_str("    ")?;
+    }
+
+    Ok(())
+}
+
+fn write_compound(
+    f: &mut impl Write,
+    compound: &NbtCompound<'_>,
+    indent: Option<usize>,
+) -> fmt::Result {
+    f.write_char('{')?;
+    for (i, (key, value)) in compound.0.iter().enumerate() {
+        if i > 0 {
+            f.write_char(',')?;
+        }
+        if let Some(level) = indent {
+            write_newline(f, level + 1)?;
+        }
+
+        write_key(f, key)?;
+        f.write_char(':')?;
+        if indent.is_some() {
+            f.write_char(' ')?;
+        }
+        write_value(f, value, indent.map(|level| level + 1))?;
+    }
+    if let Some(level) = indent.filter(|_| !compound.0.is_empty()) {
+        write_newline(f, level)?;
+    }
+    f.write_char('}')
+}
+
+// One tag per line, for logs and dumps
+pub fn to_pretty_string(compound: &NbtCompound<'_>) -> String {
+    let mut output = String::new();
+    write_compound(&mut output, compound, Some(0)).expect("Writing to a String can't fail");
+
+    output
+}
+
+impl fmt::Display for Nbt<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_value(f, self, None)
+    }
+}
+
+impl fmt::Display for NbtCompound<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_compound(f, self, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn every_tag() -> NbtCompound<'static> {
+        let mut nested = NbtCompound::default();
+        nested.set_double("d", -0.5);
+
+        let mut compound = NbtCompound::default();
+        compound.set_byte("byte", 0xff);
+        compound.set_short("short", 300);
+        compound.set_int("int", -7);
+        compound.set_long("long", 1 << 40);
+        compound.set_float("float", 1.5);
+        compound.set_double("double", 2.0);
+        compound.set_byte_array("bytes", vec![1, 0x80]);
+        compound.set_string("string", "say \"hi\"");
+        compound.set_string("with space", "it's \\");
+        compound.set_list(
+            "list",
+            vec![Nbt::Compound(nested), Nbt::Compound(NbtCompound::default())],
+        );
+        compound.set_list("empty", vec![]);
+        compound.set_int_array("ints", vec![1, -2]);
+        compound.set_long_array("longs", vec![3]);
+
+        compound
+    }
+
+    #[test]
+    fn parses_every_tag() {
+        let input = r#"{
+            byte: -1b, short: 300s, int: -7, long: 1099511627776L, float: 1.5f, double: 2.0,
+            bytes: [B; 1b, -128b], string: 'say "hi"', "with space": "it's \\",
+            list: [{d: -0.5d}, {}], empty: [], ints: [I; 1, -2], longs: [L; 3L]
+        }"#;
+
+        assert_eq!(parse_compound(input).unwrap(), every_tag());
+    }
+
+    #[test]
+    fn compact_form_round_trips() {
+        let compound = every_tag();
+
+        assert_eq!(parse_compound(&compound.to_string()).unwrap(), compound);
+    }
+
+    #[test]
+    fn pretty_form_round_trips() {
+        let compound = every_tag();
+
+        assert_eq!(
+            parse_compound(&to_pretty_string(&compound)).unwrap(),
+            compound
+        );
+    }
+
+    #[test]
+    fn writes_like_vanilla() {
+        let mut compound = NbtCompound::default();
+        compound.set_byte("a", 1);
+        compound.set_string("b c", "d");
+        compound.set_long_array("e", vec![1, 2]);
+
+        assert_eq!(compound.to_string(), r#"{a:1b,"b c":"d",e:[L;1L,2L]}"#);
+    }
+
+    #[test]
+    fn unquoted_words_pick_a_type() {
+        assert_eq!(parse("1").unwrap(), Nbt::Int(1));
+        assert_eq!(parse("1.5").unwrap(), Nbt::Double(1.5));
+        assert_eq!(parse("1e3f").unwrap(), Nbt::Float(1000.0));
+        assert_eq!(parse("true").unwrap(), Nbt::Byte(1));
+        assert_eq!(parse("300b").unwrap(), Nbt::String("300b".into()));
+        assert!(parse("minecraft:stone").is_err());
+        assert_eq!(parse("stone").unwrap(), Nbt::String("stone".into()));
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for input in [
+            "{a:1",
+            "{a 1}",
+            "{a:1}}",
+            "[1, 2L]",
+            "[I; 1, 2L]",
+            "\"open",
+            r#""\n""#,
+            "",
+        ] {
+            let error = parse(input).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{}", input);
+        }
+    }
+
+    fn nested_lists(depth: usize) -> String {
+        "[".repeat(depth) + &"]".repeat(depth)
+    }
+
+    #[test]
+    fn accepts_max_depth() {
+        let max_depth = NbtLimits::default().max_depth;
+
+        assert!(parse(&nested_lists(max_depth)).is_ok());
+        assert!(parse(&("{a:".repeat(max_depth) + "1" + &"}".repeat(max_depth))).is_ok());
+    }
+
+    #[test]
+    fn rejects_deeper_nesting() {
+        let max_depth = NbtLimits::default().max_depth;
+
+        let error = parse(&nested_lists(max_depth + 1)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("nested deeper"));
+
+        let input = "{a:".repeat(max_depth + 1) + "1" + &"}".repeat(max_depth + 1);
+        assert!(parse(&input).is_err());
+
+        // Would overflow the stack if the depth weren't checked on the way down
+        assert!(parse(&"[".repeat(1_000_000)).is_err());
+    }
+}
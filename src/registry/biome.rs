@@ -0,0 +1,127 @@
+use crate::nbt::NbtCompound;
+
+use super::RegistryElement;
+
+#[derive(Debug, Clone)]
+pub struct Biome {
+    pub has_precipitation: bool,
+    pub temperature: f32,
+    pub temperature_modifier: Option<String>,
+    pub downfall: f32,
+    pub effects: BiomeEffects,
+}
+
+// Colours are packed 0xRRGGBB values
+#[derive(Debug, Clone, Default)]
+pub struct BiomeEffects {
+    pub fog_color: i32,
+    pub water_color: i32,
+    pub water_fog_color: i32,
+    pub sky_color: i32,
+    pub foliage_color: Option<i32>,
+    pub grass_color: Option<i32>,
+    pub grass_color_modifier: Option<String>,
+    pub particle: Option<BiomeParticle>,
+    pub ambient_sound: Option<String>,
+    pub mood_sound: Option<MoodSound>,
+    pub additions_sound: Option<AdditionsSound>,
+    pub music: Option<Music>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BiomeParticle {
+    pub particle_type: String,
+    pub probability: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct MoodSound {
+    pub sound: String,
+    pub tick_delay: i32,
+    pub block_search_extent: i32,
+    pub offset: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AdditionsSound {
+    pub sound: String,
+    pub tick_chance: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Music {
+    pub sound: String,
+    pub min_delay: i32,
+    pub max_delay: i32,
+    pub replace_current_music: bool,
+}
+
+impl BiomeEffects {
+    fn to_nbt(&self) -> NbtCompound<'_> {
+        let mut effects = NbtCompound::default();
+        if let Some(grass_color_modifier) = &self.grass_color_modifier {
+            effects.set_string("grass_color_modifier", grass_color_modifier);
+        }
+        if let Some(music) = &self.music {
+            let mut nbt_music = NbtCompound::default();
+            nbt_music.set_byte("replace_current_music", music.replace_current_music as u8);
+            nbt_music.set_int("max_delay", music.max_delay);
+            nbt_music.set_string("sound", &music.sound);
+            nbt_music.set_int("min_delay", music.min_delay);
+            effects.set_compound("music", nbt_music);
+        }
+        effects.set_int("sky_color", self.sky_color);
+        if let Some(ambient_sound) = &self.ambient_sound {
+            effects.set_string("ambient_sound", ambient_sound);
+        }
+        if let Some(additions_sound) = &self.additions_sound {
+            let mut nbt_additions_sound = NbtCompound::default();
+            nbt_additions_sound.set_string("sound", &additions_sound.sound);
+            nbt_additions_sound.set_double("tick_chance", additions_sound.tick_chance);
+            effects.set_compound("additions_sound", nbt_additions_sound);
+        }
+        if let Some(grass_color) = self.grass_color {
+            effects.set_int("grass_color", grass_color);
+        }
+        if let Some(foliage_color) = self.foliage_color {
+            effects.set_int("foliage_color", foliage_color);
+        }
+        if let Some(particle) = &self.particle {
+            let mut options = NbtCompound::default();
+            options.set_string("type", &particle.particle_type);
+
+            let mut nbt_particle = NbtCompound::default();
+            nbt_particle.set_float("probability", particle.probability);
+            nbt_particle.set_compound("options", options);
+            effects.set_compound("particle", nbt_particle);
+        }
+        effects.set_int("water_fog_color", self.water_fog_color);
+        effects.set_int("fog_color", self.fog_color);
+        effects.set_int("water_color", self.water_color);
+        if let Some(mood_sound) = &self.mood_sound {
+            let mut nbt_mood_sound = NbtCompound::default();
+            nbt_mood_sound.set_int("tick_delay", mood_sound.tick_delay);
+            nbt_mood_sound.set_double("offset", mood_sound.offset);
+            nbt_mood_sound.set_string("sound", &mood_sound.sound);
+            nbt_mood_sound.set_int("block_search_extent", mood_sound.block_search_extent);
+            effects.set_compound("mood_sound", nbt_mood_sound);
+        }
+
+        effects
+    }
+}
+
+impl RegistryElement for Biome {
+    fn to_nbt(&self) -> NbtCompound<'_> {
+        let mut element = NbtCompound::default();
+        element.set_compound("effects", self.effects.to_nbt());
+        element.set_byte("has_precipitation", self.has_precipitation as u8);
+        element.set_float("temperature", self.temperature);
+        element.set_float("downfall", self.downfall);
+        if let Some(temperature_modifier) = &self.temperature_modifier {
+            element.set_string("temperature_modifier", temperature_modifier);
+        }
+
+        element
+    }
+}
@@ -0,0 +1,59 @@
+use crate::nbt::{Nbt, NbtCompound};
+
+use super::RegistryElement;
+
+#[derive(Debug, Clone)]
+pub struct ChatType {
+    pub chat: ChatDecoration,
+    pub narration: ChatDecoration,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatDecoration {
+    pub translation_key: String,
+    pub parameters: Vec<String>,
+    pub style: Option<ChatStyle>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ChatStyle {
+    pub color: Option<String>,
+    pub italic: Option<bool>,
+}
+
+impl ChatDecoration {
+    fn to_nbt(&self) -> NbtCompound<'_> {
+        let mut decoration = NbtCompound::default();
+        decoration.set_string("translation_key", &self.translation_key);
+
+        if let Some(style) = &self.style {
+            let mut nbt_style = NbtCompound::default();
+            if let Some(color) = &style.color {
+                nbt_style.set_string("color", color);
+            }
+            if let Some(italic) = style.italic {
+                nbt_style.set_byte("italic", italic as u8);
+            }
+            decoration.set_compound("style", nbt_style);
+        }
+        decoration.set_list(
+            "parameters",
+            self.parameters
+                .iter()
+                .map(|parameter| Nbt::String(parameter.into()))
+                .collect(),
+        );
+
+        decoration
+    }
+}
+
+impl RegistryElement for ChatType {
+    fn to_nbt(&self) -> NbtCompound<'_> {
+        let mut element = NbtCompound::default();
+        element.set_compound("chat", self.chat.to_nbt());
+        element.set_compound("narration", self.narration.to_nbt());
+
+        element
+    }
+}
@@ -0,0 +1,29 @@
+use crate::nbt::NbtCompound;
+
+use super::RegistryElement;
+
+#[derive(Debug, Clone)]
+pub struct DamageType {
+    pub message_id: String,
+    pub scaling: String,
+    pub exhaustion: f32,
+    pub effects: Option<String>,
+    pub death_message_type: Option<String>,
+}
+
+impl RegistryElement for DamageType {
+    fn to_nbt(&self) -> NbtCompound<'_> {
+        let mut element = NbtCompound::default();
+        if let Some(effects) = &self.effects {
+            element.set_string("effects", effects);
+        }
+        element.set_string("scaling", &self.scaling);
+        element.set_float("exhaustion", self.exhaustion);
+        element.set_string("message_id", &self.message_id);
+        if let Some(death_message_type) = &self.death_message_type {
+            element.set_string("death_message_type", death_message_type);
+        }
+
+        element
+    }
+}
@@ -0,0 +1,80 @@
+use crate::nbt::NbtCompound;
+
+use super::RegistryElement;
+
+#[derive(Debug, Clone)]
+pub enum IntProvider {
+    Constant(i32),
+    Uniform {
+        min_inclusive: i32,
+        max_inclusive: i32,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct DimensionType {
+    pub fixed_time: Option<i64>,
+    pub has_skylight: bool,
+    pub has_ceiling: bool,
+    pub ultrawarm: bool,
+    pub natural: bool,
+    pub coordinate_scale: f64,
+    pub bed_works: bool,
+    pub respawn_anchor_works: bool,
+    pub min_y: i32,
+    pub height: i32,
+    pub logical_height: i32,
+    pub infiniburn: String,
+    pub effects: String,
+    pub ambient_light: f32,
+    pub piglin_safe: bool,
+    pub has_raids: bool,
+    pub monster_spawn_light_level: IntProvider,
+    pub monster_spawn_block_light_limit: i32,
+}
+
+impl RegistryElement for DimensionType {
+    fn to_nbt(&self) -> NbtCompound<'_> {
+        let mut element = NbtCompound::default();
+        element.set_byte("piglin_safe", self.piglin_safe as u8);
+        element.set_byte("natural", self.natural as u8);
+        element.set_float("ambient_light", self.ambient_light);
+        element.set_int(
+            "monster_spawn_block_light_limit",
+            self.monster_spawn_block_light_limit,
+        );
+        element.set_string("infiniburn", &self.infiniburn);
+        element.set_byte("respawn_anchor_works", self.respawn_anchor_works as u8);
+        element.set_byte("has_skylight", self.has_skylight as u8);
+        element.set_byte("bed_works", self.bed_works as u8);
+        element.set_string("effects", &self.effects);
+        if let Some(fixed_time) = self.fixed_time {
+            element.set_long("fixed_time", fixed_time);
+        }
+        element.set_byte("has_raids", self.has_raids as u8);
+        element.set_int("logical_height", self.logical_height);
+        element.set_double("coordinate_scale", self.coordinate_scale);
+        match self.monster_spawn_light_level {
+            IntProvider::Constant(value) => element.set_int("monster_spawn_light_level", value),
+            IntProvider::Uniform {
+                min_inclusive,
+                max_inclusive,
+            } => {
+                let mut value = NbtCompound::default();
+                value.set_int("min_inclusive", min_inclusive);
+                value.set_int("max_inclusive", max_inclusive);
+
+                let mut provider = NbtCompound::default();
+                provider.set_string("type", "minecraft:uniform");
+                provider.set_compound("value", value);
+                element.set_compound("monster_spawn_light_level", provider);
+            }
+        }
+        element.set_int("min_y", self.min_y);
+        element.set_byte("ultrawarm", self.ultrawarm as u8);
+        element.set_byte("has_ceiling", self.has_ceiling as u8);
+        element.set_int("height", self.height);
+
+        element
+    }
+}
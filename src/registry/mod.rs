@@ -0,0 +1,271 @@
+use std::io;
+use std::sync::OnceLock;
+
+use crate::nbt::{Nbt, NbtCompound, NbtFormat};
+use crate::tcp::protocol::{Protocol, PROTOCOLS};
+
+pub mod biome;
+pub mod chat_type;
+pub mod damage_type;
+pub mod dimension_type;
+mod vanilla;
+
+use biome::Biome;
+use chat_type::ChatType;
+use damage_type::DamageType;
+use dimension_type::DimensionType;
+
+// Elements set their keys in the order vanilla sends them, so our codec matches its bytes
+pub trait RegistryElement {
+    fn to_nbt(&self) -> NbtCompound<'_>;
+}
+
+// Entries keep the id they were registered with, the client gets the same ids
+#[derive(Debug, Clone)]
+pub struct Registry<T> {
+    pub name: &'static str,
+    entries: Vec<(String, T)>,
+}
+
+impl<T> Registry<T> {
+    pub fn new(name: &'static str) -> Self {
+        Registry {
+            name,
+            entries: vec![],
+        }
+    }
+
+    // Replaces the element when the name is already taken, keeping its id
+    pub fn register(&mut self, name: &str, element: T) -> i32 {
+        match self.get_id(name) {
+            Some(id) => {
+                self.entries[id as usize].1 = element;
+                id
+            }
+            None => {
+                self.entries.push((name.to_owned(), element));
+                self.entries.len() as i32 - 1
+            }
+        }
+    }
+
+    pub fn get_id(&self, name: &str) -> Option<i32> {
+        self.entries
+            .iter()
+            .position(|(entry_name, _)| entry_name == name)
+            .map(|id| id as i32)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&T> {
+        self.entries
+            .iter()
+            .find(|(entry_name, _)| entry_name == name)
+            .map(|(_, element)| element)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<T: RegistryElement> Registry<T> {
+    pub fn to_nbt(&self) -> NbtCompound<'_> {
+        let entries = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(id, (name, element))| {
+                let mut entry = NbtCompound::default();
+                entry.set_string("name", name);
+                entry.set_int("id", id as i32);
+                entry.set_compound("element", element.to_nbt());
+
+                Nbt::Compound(entry)
+            })
+            .collect();
+
+        let mut registry = NbtCompound::default();
+        registry.set_string("type", self.name);
+        registry.set_list("value", entries);
+
+        registry
+    }
+}
+
+// Armor trims are experimental, the client is fine with them being empty
+#[derive(Debug, Clone)]
+pub struct TrimPattern;
+
+#[derive(Debug, Clone)]
+pub struct TrimMaterial;
+
+impl RegistryElement for TrimPattern {
+    fn to_nbt(&self) -> NbtCompound<'_> {
+        NbtCompound::default()
+    }
+}
+
+impl RegistryElement for TrimMaterial {
+    fn to_nbt(&self) -> NbtCompound<'_> {
+        NbtCompound::default()
+    }
+}
+
+// Everything the client needs to know about data driven content, sent in Login (play)
+#[derive(Debug, Clone)]
+pub struct RegistryCodec {
+    pub trim_patterns: Registry<TrimPattern>,
+    pub trim_materials: Registry<TrimMaterial>,
+    pub chat_types: Registry<ChatType>,
+    pub dimension_types: Registry<DimensionType>,
+    pub damage_types: Registry<DamageType>,
+    pub biomes: Registry<Biome>,
+}
+
+impl RegistryCodec {
+    pub fn vanilla() -> Self {
+        RegistryCodec {
+            trim_patterns: Registry::new("minecraft:trim_pattern"),
+            trim_materials: Registry::new("minecraft:trim_material"),
+            chat_types: vanilla::chat_types(),
+            dimension_types: vanilla::dimension_types(),
+            damage_types: vanilla::damage_types(),
+            biomes: vanilla::biomes(),
+        }
+    }
+
+    pub fn to_nbt(&self) -> NbtCompound<'_> {
+        let mut codec = NbtCompound::default();
+        codec.set_compound(self.trim_patterns.name, self.trim_patterns.to_nbt());
+        codec.set_compound(self.trim_materials.name, self.trim_materials.to_nbt());
+        codec.set_compound(self.chat_types.name, self.chat_types.to_nbt());
+        codec.set_compound(self.dimension_types.name, self.dimension_types.to_nbt());
+        codec.set_compound(self.damage_types.name, self.damage_types.to_nbt());
+        codec.set_compound(self.biomes.name, self.biomes.to_nbt());
+
+        codec
+    }
+
+    pub fn pack(&self) -> io::Result<Vec<u8>> {
+        self.to_nbt().pack()
+    }
+
+    pub fn pack_as(&self, format: NbtFormat) -> io::Result<Vec<u8>> {
+        self.to_nbt().pack_as(format)
+    }
+
+    // The registries are kept as 1.19.4 has them, newer clients get what they added on top
+    pub fn for_protocol(&self, protocol: &Protocol) -> RegistryCodec {
+        let mut codec = self.clone();
+        if protocol.damage_types_1_20 {
+            vanilla::add_1_20_damage_types(&mut codec.damage_types);
+        }
+
+        codec
+    }
+}
+
+static REGISTRY_CODEC: OnceLock<RegistryCodec> = OnceLock::new();
+
+// Custom dimension types and biomes have to be registered before the server starts
+pub fn init_registry_codec(codec: RegistryCodec) {
+    if REGISTRY_CODEC.set(codec).is_err() {
+        panic!("Registry codec was already initialized");
+    }
+}
+
+pub fn get_registry_codec() -> &'static RegistryCodec {
+    REGISTRY_CODEC.get_or_init(RegistryCodec::vanilla)
+}
+
+// The codec doesn't change once the server runs, so it's only packed once per protocol,
+// in whichever root format that protocol expects
+pub fn get_packed_registry_codec(protocol: &Protocol) -> &'static [u8] {
+    static PACKED: OnceLock<Vec<Vec<u8>>> = OnceLock::new();
+
+    let packed = PACKED.get_or_init(|| {
+        PROTOCOLS
+            .iter()
+            .map(|protocol| {
+                get_registry_codec()
+                    .for_protocol(protocol)
+                    .pack_as(protocol.nbt_format)
+                    .expect("Registry codec is always serializable")
+            })
+            .collect()
+    });
+
+    let index = PROTOCOLS
+        .iter()
+        .position(|supported| supported.version == protocol.version)
+        .expect("Only supported protocols get this far");
+
+    &packed[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nbt::{NbtLimits, ReadNbtExt};
+    use crate::tcp::protocol::get_protocol;
+
+    // Captured from a 1.19.4 server
+    const VANILLA_CODEC: &[u8] = include_bytes!("../files/registryCodec.bin");
+
+    #[test]
+    fn vanilla_codec_matches_1_19_4() {
+        let fixture = (&VANILLA_CODEC[..]).read_nbt().unwrap();
+
+        assert_eq!(RegistryCodec::vanilla().to_nbt(), fixture);
+    }
+
+    // Compound keys keep their insertion order, so packing gives the same bytes every time
+    #[test]
+    fn packing_is_deterministic() {
+        let codec = RegistryCodec::vanilla();
+
+        assert_eq!(codec.pack().unwrap(), codec.pack().unwrap());
+        assert_eq!(
+            codec.pack().unwrap(),
+            RegistryCodec::vanilla().pack().unwrap()
+        );
+    }
+
+    #[test]
+    fn vanilla_codec_matches_1_19_4_bytes() {
+        assert!(
+            RegistryCodec::vanilla().pack().unwrap() == VANILLA_CODEC,
+            "Packed codec differs from the one vanilla sends"
+        );
+    }
+
+    #[test]
+    fn packed_codec_follows_protocol_format() {
+        for protocol in PROTOCOLS {
+            let mut packed = get_packed_registry_codec(protocol);
+            let codec = packed
+                .read_nbt_as(protocol.nbt_format, NbtLimits::default())
+                .unwrap();
+
+            assert!(packed.is_empty());
+            assert_eq!(codec, get_registry_codec().for_protocol(protocol).to_nbt());
+        }
+    }
+
+    #[test]
+    fn newer_protocols_get_their_damage_types() {
+        let codec = RegistryCodec::vanilla();
+
+        let damage_types = codec.for_protocol(get_protocol(762).unwrap()).damage_types;
+        assert_eq!(damage_types.get_id("minecraft:outside_border"), None);
+
+        let damage_types = codec.for_protocol(get_protocol(763).unwrap()).damage_types;
+        assert_eq!(damage_types.len(), codec.damage_types.len() + 2);
+        assert_eq!(damage_types.get_id("minecraft:generic_kill"), Some(42));
+        assert_eq!(damage_types.get_id("minecraft:outside_border"), Some(43));
+    }
+}
@@ -0,0 +1,2616 @@
+// Vanilla 1.19.4 registry contents, as sent by the notchian server
+
+use super::biome::{AdditionsSound, Biome, BiomeEffects, BiomeParticle, MoodSound, Music};
+use super::chat_type::{ChatDecoration, ChatStyle, ChatType};
+use super::damage_type::DamageType;
+use super::dimension_type::{DimensionType, IntProvider};
+use super::Registry;
+
+pub fn chat_types() -> Registry<ChatType> {
+    let mut registry = Registry::new("minecraft:chat_type");
+    registry.register(
+        "minecraft:chat",
+        ChatType {
+            chat: ChatDecoration {
+                translation_key: "chat.type.text".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+            narration: ChatDecoration {
+                translation_key: "chat.type.text.narrate".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:emote_command",
+        ChatType {
+            chat: ChatDecoration {
+                translation_key: "chat.type.emote".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+            narration: ChatDecoration {
+                translation_key: "chat.type.emote".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:msg_command_incoming",
+        ChatType {
+            chat: ChatDecoration {
+                translation_key: "commands.message.display.incoming".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: Some(ChatStyle {
+                    color: Some("gray".to_owned()),
+                    italic: Some(true),
+                }),
+            },
+            narration: ChatDecoration {
+                translation_key: "chat.type.text.narrate".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:msg_command_outgoing",
+        ChatType {
+            chat: ChatDecoration {
+                translation_key: "commands.message.display.outgoing".to_owned(),
+                parameters: vec!["target".to_owned(), "content".to_owned()],
+                style: Some(ChatStyle {
+                    color: Some("gray".to_owned()),
+                    italic: Some(true),
+                }),
+            },
+            narration: ChatDecoration {
+                translation_key: "chat.type.text.narrate".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:say_command",
+        ChatType {
+            chat: ChatDecoration {
+                translation_key: "chat.type.announcement".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+            narration: ChatDecoration {
+                translation_key: "chat.type.text.narrate".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:team_msg_command_incoming",
+        ChatType {
+            chat: ChatDecoration {
+                translation_key: "chat.type.team.text".to_owned(),
+                parameters: vec![
+                    "target".to_owned(),
+                    "sender".to_owned(),
+                    "content".to_owned(),
+                ],
+                style: None,
+            },
+            narration: ChatDecoration {
+                translation_key: "chat.type.text.narrate".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:team_msg_command_outgoing",
+        ChatType {
+            chat: ChatDecoration {
+                translation_key: "chat.type.team.sent".to_owned(),
+                parameters: vec![
+                    "target".to_owned(),
+                    "sender".to_owned(),
+                    "content".to_owned(),
+                ],
+                style: None,
+            },
+            narration: ChatDecoration {
+                translation_key: "chat.type.text.narrate".to_owned(),
+                parameters: vec!["sender".to_owned(), "content".to_owned()],
+                style: None,
+            },
+        },
+    );
+
+    registry
+}
+
+pub fn dimension_types() -> Registry<DimensionType> {
+    let mut registry = Registry::new("minecraft:dimension_type");
+    registry.register(
+        "minecraft:overworld",
+        DimensionType {
+            fixed_time: None,
+            has_skylight: true,
+            has_ceiling: false,
+            ultrawarm: false,
+            natural: true,
+            coordinate_scale: 1.0,
+            bed_works: true,
+            respawn_anchor_works: false,
+            min_y: -64,
+            height: 384,
+            logical_height: 384,
+            infiniburn: "#minecraft:infiniburn_overworld".to_owned(),
+            effects: "minecraft:overworld".to_owned(),
+            ambient_light: 0.0,
+            piglin_safe: false,
+            has_raids: true,
+            monster_spawn_light_level: IntProvider::Uniform {
+                min_inclusive: 0,
+                max_inclusive: 7,
+            },
+            monster_spawn_block_light_limit: 0,
+        },
+    );
+    registry.register(
+        "minecraft:overworld_caves",
+        DimensionType {
+            fixed_time: None,
+            has_skylight: true,
+            has_ceiling: true,
+            ultrawarm: false,
+            natural: true,
+            coordinate_scale: 1.0,
+            bed_works: true,
+            respawn_anchor_works: false,
+            min_y: -64,
+            height: 384,
+            logical_height: 384,
+            infiniburn: "#minecraft:infiniburn_overworld".to_owned(),
+            effects: "minecraft:overworld".to_owned(),
+            ambient_light: 0.0,
+            piglin_safe: false,
+            has_raids: true,
+            monster_spawn_light_level: IntProvider::Uniform {
+                min_inclusive: 0,
+                max_inclusive: 7,
+            },
+            monster_spawn_block_light_limit: 0,
+        },
+    );
+    registry.register(
+        "minecraft:the_end",
+        DimensionType {
+            fixed_time: Some(6000),
+            has_skylight: false,
+            has_ceiling: false,
+            ultrawarm: false,
+            natural: false,
+            coordinate_scale: 1.0,
+            bed_works: false,
+            respawn_anchor_works: false,
+            min_y: 0,
+            height: 256,
+            logical_height: 256,
+            infiniburn: "#minecraft:infiniburn_end".to_owned(),
+            effects: "minecraft:the_end".to_owned(),
+            ambient_light: 0.0,
+            piglin_safe: false,
+            has_raids: true,
+            monster_spawn_light_level: IntProvider::Uniform {
+                min_inclusive: 0,
+                max_inclusive: 7,
+            },
+            monster_spawn_block_light_limit: 0,
+        },
+    );
+    registry.register(
+        "minecraft:the_nether",
+        DimensionType {
+            fixed_time: Some(18000),
+            has_skylight: false,
+            has_ceiling: true,
+            ultrawarm: true,
+            natural: false,
+            coordinate_scale: 8.0,
+            bed_works: false,
+            respawn_anchor_works: true,
+            min_y: 0,
+            height: 256,
+            logical_height: 128,
+            infiniburn: "#minecraft:infiniburn_nether".to_owned(),
+            effects: "minecraft:the_nether".to_owned(),
+            ambient_light: 0.1,
+            piglin_safe: true,
+            has_raids: false,
+            monster_spawn_light_level: IntProvider::Constant(7),
+            monster_spawn_block_light_limit: 15,
+        },
+    );
+
+    registry
+}
+
+pub fn damage_types() -> Registry<DamageType> {
+    let mut registry = Registry::new("minecraft:damage_type");
+    registry.register(
+        "minecraft:arrow",
+        DamageType {
+            message_id: "arrow".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:bad_respawn_point",
+        DamageType {
+            message_id: "badRespawnPoint".to_owned(),
+            scaling: "always".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: Some("intentional_game_design".to_owned()),
+        },
+    );
+    registry.register(
+        "minecraft:cactus",
+        DamageType {
+            message_id: "cactus".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:cramming",
+        DamageType {
+            message_id: "cramming".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:dragon_breath",
+        DamageType {
+            message_id: "dragonBreath".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:drown",
+        DamageType {
+            message_id: "drown".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: Some("drowning".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:dry_out",
+        DamageType {
+            message_id: "dryout".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:explosion",
+        DamageType {
+            message_id: "explosion".to_owned(),
+            scaling: "always".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:fall",
+        DamageType {
+            message_id: "fall".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: Some("fall_variants".to_owned()),
+        },
+    );
+    registry.register(
+        "minecraft:falling_anvil",
+        DamageType {
+            message_id: "anvil".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:falling_block",
+        DamageType {
+            message_id: "fallingBlock".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:falling_stalactite",
+        DamageType {
+            message_id: "fallingStalactite".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:fireball",
+        DamageType {
+            message_id: "fireball".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: Some("burning".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:fireworks",
+        DamageType {
+            message_id: "fireworks".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:fly_into_wall",
+        DamageType {
+            message_id: "flyIntoWall".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:freeze",
+        DamageType {
+            message_id: "freeze".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: Some("freezing".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:generic",
+        DamageType {
+            message_id: "generic".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:hot_floor",
+        DamageType {
+            message_id: "hotFloor".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: Some("burning".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:in_fire",
+        DamageType {
+            message_id: "inFire".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: Some("burning".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:in_wall",
+        DamageType {
+            message_id: "inWall".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:indirect_magic",
+        DamageType {
+            message_id: "indirectMagic".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:lava",
+        DamageType {
+            message_id: "lava".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: Some("burning".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:lightning_bolt",
+        DamageType {
+            message_id: "lightningBolt".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:magic",
+        DamageType {
+            message_id: "magic".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:mob_attack",
+        DamageType {
+            message_id: "mob".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:mob_attack_no_aggro",
+        DamageType {
+            message_id: "mob".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:mob_projectile",
+        DamageType {
+            message_id: "mob".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:on_fire",
+        DamageType {
+            message_id: "onFire".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: Some("burning".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:out_of_world",
+        DamageType {
+            message_id: "outOfWorld".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:player_attack",
+        DamageType {
+            message_id: "player".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:player_explosion",
+        DamageType {
+            message_id: "explosion.player".to_owned(),
+            scaling: "always".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:sonic_boom",
+        DamageType {
+            message_id: "sonic_boom".to_owned(),
+            scaling: "always".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:stalagmite",
+        DamageType {
+            message_id: "stalagmite".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:starve",
+        DamageType {
+            message_id: "starve".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:sting",
+        DamageType {
+            message_id: "sting".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:sweet_berry_bush",
+        DamageType {
+            message_id: "sweetBerryBush".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: Some("poking".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:thorns",
+        DamageType {
+            message_id: "thorns".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: Some("thorns".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:thrown",
+        DamageType {
+            message_id: "thrown".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:trident",
+        DamageType {
+            message_id: "trident".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:unattributed_fireball",
+        DamageType {
+            message_id: "onFire".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: Some("burning".to_owned()),
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:wither",
+        DamageType {
+            message_id: "wither".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:wither_skull",
+        DamageType {
+            message_id: "witherSkull".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.1,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+
+    registry
+}
+
+// 1.20 added these and its clients won't join without them
+pub fn add_1_20_damage_types(registry: &mut Registry<DamageType>) {
+    registry.register(
+        "minecraft:generic_kill",
+        DamageType {
+            message_id: "genericKill".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+    registry.register(
+        "minecraft:outside_border",
+        DamageType {
+            message_id: "outsideBorder".to_owned(),
+            scaling: "when_caused_by_living_non_player".to_owned(),
+            exhaustion: 0.0,
+            effects: None,
+            death_message_type: None,
+        },
+    );
+}
+
+pub fn biomes() -> Registry<Biome> {
+    let mut registry = Registry::new("minecraft:worldgen/biome");
+    registry.register(
+        "minecraft:badlands",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: Some(10387789),
+                grass_color: Some(9470285),
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:bamboo_jungle",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.95,
+            temperature_modifier: None,
+            downfall: 0.9,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7842047,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.jungle_and_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:basalt_deltas",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 6840176,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: Some(BiomeParticle {
+                    particle_type: "minecraft:white_ash".to_owned(),
+                    probability: 0.118093334,
+                }),
+                ambient_sound: Some("minecraft:ambient.basalt_deltas.loop".to_owned()),
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.basalt_deltas.mood".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: Some(AdditionsSound {
+                    sound: "minecraft:ambient.basalt_deltas.additions".to_owned(),
+                    tick_chance: 0.0111,
+                }),
+                music: Some(Music {
+                    sound: "minecraft:music.nether.basalt_deltas".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:beach",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.8,
+            temperature_modifier: None,
+            downfall: 0.4,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7907327,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:birch_forest",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.6,
+            temperature_modifier: None,
+            downfall: 0.6,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8037887,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.jungle_and_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:cold_ocean",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4020182,
+                water_fog_color: 329011,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:crimson_forest",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 3343107,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: Some(BiomeParticle {
+                    particle_type: "minecraft:crimson_spore".to_owned(),
+                    probability: 0.025,
+                }),
+                ambient_sound: Some("minecraft:ambient.crimson_forest.loop".to_owned()),
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.crimson_forest.mood".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: Some(AdditionsSound {
+                    sound: "minecraft:ambient.crimson_forest.additions".to_owned(),
+                    tick_chance: 0.0111,
+                }),
+                music: Some(Music {
+                    sound: "minecraft:music.nether.crimson_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:dark_forest",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.7,
+            temperature_modifier: None,
+            downfall: 0.8,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7972607,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: Some("dark_forest".to_owned()),
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.jungle_and_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:deep_cold_ocean",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4020182,
+                water_fog_color: 329011,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:deep_dark",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.8,
+            temperature_modifier: None,
+            downfall: 0.4,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7907327,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.deep_dark".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:deep_frozen_ocean",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: Some("frozen".to_owned()),
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 3750089,
+                water_fog_color: 329011,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:deep_lukewarm_ocean",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4566514,
+                water_fog_color: 267827,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:deep_ocean",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:desert",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:dripstone_caves",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.8,
+            temperature_modifier: None,
+            downfall: 0.4,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7907327,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.dripstone_caves".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:end_barrens",
+        Biome {
+            has_precipitation: false,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 10518688,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 0,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:end_highlands",
+        Biome {
+            has_precipitation: false,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 10518688,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 0,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:end_midlands",
+        Biome {
+            has_precipitation: false,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 10518688,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 0,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:eroded_badlands",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: Some(10387789),
+                grass_color: Some(9470285),
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:flower_forest",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.7,
+            temperature_modifier: None,
+            downfall: 0.8,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7972607,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.jungle_and_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:forest",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.7,
+            temperature_modifier: None,
+            downfall: 0.8,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7972607,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.jungle_and_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:frozen_ocean",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.0,
+            temperature_modifier: Some("frozen".to_owned()),
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 3750089,
+                water_fog_color: 329011,
+                sky_color: 8364543,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:frozen_peaks",
+        Biome {
+            has_precipitation: true,
+            temperature: -0.7,
+            temperature_modifier: None,
+            downfall: 0.9,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8756735,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.frozen_peaks".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:frozen_river",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.0,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 3750089,
+                water_fog_color: 329011,
+                sky_color: 8364543,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:grove",
+        Biome {
+            has_precipitation: true,
+            temperature: -0.2,
+            temperature_modifier: None,
+            downfall: 0.8,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8495359,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.grove".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:ice_spikes",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.0,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8364543,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:jagged_peaks",
+        Biome {
+            has_precipitation: true,
+            temperature: -0.7,
+            temperature_modifier: None,
+            downfall: 0.9,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8756735,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.jagged_peaks".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:jungle",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.95,
+            temperature_modifier: None,
+            downfall: 0.9,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7842047,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.jungle_and_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:lukewarm_ocean",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4566514,
+                water_fog_color: 267827,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:lush_caves",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.lush_caves".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:mangrove_swamp",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.8,
+            temperature_modifier: None,
+            downfall: 0.9,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 3832426,
+                water_fog_color: 5077600,
+                sky_color: 7907327,
+                foliage_color: Some(9285927),
+                grass_color: None,
+                grass_color_modifier: Some("swamp".to_owned()),
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.swamp".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:meadow",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.8,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 937679,
+                water_fog_color: 329011,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.meadow".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:mushroom_fields",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.9,
+            temperature_modifier: None,
+            downfall: 1.0,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7842047,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:nether_wastes",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 3344392,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: Some("minecraft:ambient.nether_wastes.loop".to_owned()),
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.nether_wastes.mood".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: Some(AdditionsSound {
+                    sound: "minecraft:ambient.nether_wastes.additions".to_owned(),
+                    tick_chance: 0.0111,
+                }),
+                music: Some(Music {
+                    sound: "minecraft:music.nether.nether_wastes".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:ocean",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:old_growth_birch_forest",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.6,
+            temperature_modifier: None,
+            downfall: 0.6,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8037887,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.jungle_and_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:old_growth_pine_taiga",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.3,
+            temperature_modifier: None,
+            downfall: 0.8,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8168447,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.old_growth_taiga".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:old_growth_spruce_taiga",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.25,
+            temperature_modifier: None,
+            downfall: 0.8,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8233983,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.old_growth_taiga".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:plains",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.8,
+            temperature_modifier: None,
+            downfall: 0.4,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7907327,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:river",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:savanna",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:savanna_plateau",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:small_end_islands",
+        Biome {
+            has_precipitation: false,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 10518688,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 0,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:snowy_beach",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.05,
+            temperature_modifier: None,
+            downfall: 0.3,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4020182,
+                water_fog_color: 329011,
+                sky_color: 8364543,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:snowy_plains",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.0,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8364543,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:snowy_slopes",
+        Biome {
+            has_precipitation: true,
+            temperature: -0.3,
+            temperature_modifier: None,
+            downfall: 0.9,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8560639,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.snowy_slopes".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:snowy_taiga",
+        Biome {
+            has_precipitation: true,
+            temperature: -0.5,
+            temperature_modifier: None,
+            downfall: 0.4,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4020182,
+                water_fog_color: 329011,
+                sky_color: 8625919,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:soul_sand_valley",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 1787717,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: Some(BiomeParticle {
+                    particle_type: "minecraft:ash".to_owned(),
+                    probability: 0.00625,
+                }),
+                ambient_sound: Some("minecraft:ambient.soul_sand_valley.loop".to_owned()),
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.soul_sand_valley.mood".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: Some(AdditionsSound {
+                    sound: "minecraft:ambient.soul_sand_valley.additions".to_owned(),
+                    tick_chance: 0.0111,
+                }),
+                music: Some(Music {
+                    sound: "minecraft:music.nether.soul_sand_valley".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:sparse_jungle",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.95,
+            temperature_modifier: None,
+            downfall: 0.8,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7842047,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.jungle_and_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:stony_peaks",
+        Biome {
+            has_precipitation: true,
+            temperature: 1.0,
+            temperature_modifier: None,
+            downfall: 0.3,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7776511,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.stony_peaks".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:stony_shore",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.2,
+            temperature_modifier: None,
+            downfall: 0.3,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8233727,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:sunflower_plains",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.8,
+            temperature_modifier: None,
+            downfall: 0.4,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7907327,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:swamp",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.8,
+            temperature_modifier: None,
+            downfall: 0.9,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 6388580,
+                water_fog_color: 2302743,
+                sky_color: 7907327,
+                foliage_color: Some(6975545),
+                grass_color: None,
+                grass_color_modifier: Some("swamp".to_owned()),
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: Some(Music {
+                    sound: "minecraft:music.overworld.swamp".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:taiga",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.25,
+            temperature_modifier: None,
+            downfall: 0.8,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8233983,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:the_end",
+        Biome {
+            has_precipitation: false,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 10518688,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 0,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:the_void",
+        Biome {
+            has_precipitation: false,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:warm_ocean",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.5,
+            temperature_modifier: None,
+            downfall: 0.5,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4445678,
+                water_fog_color: 270131,
+                sky_color: 8103167,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:warped_forest",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 1705242,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: Some(BiomeParticle {
+                    particle_type: "minecraft:warped_spore".to_owned(),
+                    probability: 0.01428,
+                }),
+                ambient_sound: Some("minecraft:ambient.warped_forest.loop".to_owned()),
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.warped_forest.mood".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: Some(AdditionsSound {
+                    sound: "minecraft:ambient.warped_forest.additions".to_owned(),
+                    tick_chance: 0.0111,
+                }),
+                music: Some(Music {
+                    sound: "minecraft:music.nether.warped_forest".to_owned(),
+                    min_delay: 12000,
+                    max_delay: 24000,
+                    replace_current_music: false,
+                }),
+            },
+        },
+    );
+    registry.register(
+        "minecraft:windswept_forest",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.2,
+            temperature_modifier: None,
+            downfall: 0.3,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8233727,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:windswept_gravelly_hills",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.2,
+            temperature_modifier: None,
+            downfall: 0.3,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8233727,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:windswept_hills",
+        Biome {
+            has_precipitation: true,
+            temperature: 0.2,
+            temperature_modifier: None,
+            downfall: 0.3,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 8233727,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:windswept_savanna",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: None,
+                grass_color: None,
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+    registry.register(
+        "minecraft:wooded_badlands",
+        Biome {
+            has_precipitation: false,
+            temperature: 2.0,
+            temperature_modifier: None,
+            downfall: 0.0,
+            effects: BiomeEffects {
+                fog_color: 12638463,
+                water_color: 4159204,
+                water_fog_color: 329011,
+                sky_color: 7254527,
+                foliage_color: Some(10387789),
+                grass_color: Some(9470285),
+                grass_color_modifier: None,
+                particle: None,
+                ambient_sound: None,
+                mood_sound: Some(MoodSound {
+                    sound: "minecraft:ambient.cave".to_owned(),
+                    tick_delay: 6000,
+                    block_search_extent: 8,
+                    offset: 2.0,
+                }),
+                additions_sound: None,
+                music: None,
+            },
+        },
+    );
+
+    registry
+}
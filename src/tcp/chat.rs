@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "JsonComponent")]
+pub struct TextComponent {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extra: Vec<TextComponent>,
+}
+
+impl TextComponent {
+    pub fn text(text: &str) -> Self {
+        TextComponent {
+            text: text.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn color(mut self, color: &str) -> Self {
+        self.color = Some(color.to_owned());
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Text components are always serializable")
+    }
+
+    // What's left without styling, for clients that only take a string
+    pub fn to_plain(&self) -> String {
+        let mut text = self.text.clone();
+        for extra in &self.extra {
+            text.push_str(&extra.to_plain());
+        }
+
+        text
+    }
+}
+
+// JSON components are used as they are, anything else is plain text like vanilla's motd
+impl FromStr for TextComponent {
+    type Err = serde_json::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.starts_with(['{', '[', '"']) {
+            serde_json::from_str(value)
+        } else {
+            Ok(TextComponent::text(value))
+        }
+    }
+}
+
+// Every shape the client accepts, an array is its first component followed by the others
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonComponent {
+    Text(String),
+    List(Vec<TextComponent>),
+    Object {
+        #[serde(default)]
+        text: String,
+        color: Option<String>,
+        bold: Option<bool>,
+        italic: Option<bool>,
+        #[serde(default)]
+        extra: Vec<TextComponent>,
+    },
+}
+
+impl From<JsonComponent> for TextComponent {
+    fn from(component: JsonComponent) -> Self {
+        match component {
+            JsonComponent::Text(text) => TextComponent::text(&text),
+            JsonComponent::List(components) => {
+                let mut components = components.into_iter();
+                let mut first = components.next().unwrap_or_default();
+                first.extra.extend(components);
+
+                first
+            }
+            JsonComponent::Object {
+                text,
+                color,
+                bold,
+                italic,
+                extra,
+            } => TextComponent {
+                text,
+                color,
+                bold,
+                italic,
+                extra,
+            },
+        }
+    }
+}
+
+impl From<&str> for TextComponent {
+    fn from(text: &str) -> Self {
+        TextComponent::text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_text() {
+        let component: TextComponent = "A \u{a7}cred\u{a7}r server".parse().unwrap();
+        assert_eq!(component, TextComponent::text("A \u{a7}cred\u{a7}r server"));
+    }
+
+    #[test]
+    fn parses_json_shapes() {
+        let expected = TextComponent {
+            extra: vec![TextComponent::text("world").color("gold")],
+            ..TextComponent::text("hello ")
+        };
+
+        let object: TextComponent =
+            r#"{"text": "hello ", "extra": [{"text": "world", "color": "gold"}]}"#
+                .parse()
+                .unwrap();
+        assert_eq!(object, expected);
+
+        let list: TextComponent =
+            r#"["hello ", {"text": "world", "color": "gold"}]"#.parse().unwrap();
+        assert_eq!(list, expected);
+
+        let string: TextComponent = r#""hello ""#.parse().unwrap();
+        assert_eq!(string, TextComponent::text("hello "));
+    }
+
+    #[test]
+    fn rejects_broken_json() {
+        assert!(r#"{"text": "hello"#.parse::<TextComponent>().is_err());
+        assert!("[1, 2]".parse::<TextComponent>().is_err());
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let component = TextComponent {
+            bold: Some(true),
+            extra: vec![TextComponent::text("\"quoted\"").color("red")],
+            ..TextComponent::text("motd")
+        };
+
+        assert_eq!(
+            component.to_json().parse::<TextComponent>().unwrap(),
+            component
+        );
+    }
+
+    #[test]
+    fn flattens_to_plain_text() {
+        let component: TextComponent =
+            r#"["a", {"text": "b", "extra": ["c"]}, "d"]"#.parse().unwrap();
+        assert_eq!(component.to_plain(), "abcd");
+    }
+}
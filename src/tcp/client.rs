@@ -1,87 +1,241 @@
 use crate::blocks::chunk::ChunkPos;
 use crate::blocks::world::get_world;
+use crate::config::get_config;
+use crate::tcp::crypto::{CipherReader, Encryptor};
 use crate::tcp::packet::C2s;
+use crate::tcp::protocol::Protocol;
+use crate::tcp::session::ProfileProperty;
 use crate::tcp::state::State;
-use crate::tcp::{mapper, AsyncWriteOwnExt};
-use crate::{log, measure};
+use crate::tcp::{compression, crypto, event, mapper, protocol, server, session};
+use crate::{log, measure, registry};
+use base64::Engine;
 use std::borrow::Borrow;
+use std::fs;
 use std::io::{self, Read, Write};
-use std::sync::Arc;
-use std::time::{Duration, SystemTime};
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter, Take};
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader, BufWriter, Take};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::select;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::sync::{Mutex, RwLock};
 
-use super::packet::{Players, S2c, Version};
+use super::chat::TextComponent;
+use super::packet::{PlayerInfo, PlayerSample, Players, S2c, Status, Version};
+use super::server::ClientInfo;
 use super::{utils, AsyncReadOwnExt};
 
+// How often keep alives are sent, the timeout to answer them is configurable
+pub const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(10);
+
+// Vanilla never lists more players than this in the server list
+const MAX_STATUS_SAMPLE: usize = 12;
+
+const FAVICON_PATH: &str = "server-icon.png";
+
+const DIMENSION_NAMES: &[&str] = &[
+    "minecraft:overworld",
+    "minecraft:the_end",
+    "minecraft:the_nether",
+];
+
+// Players always join in the overworld for now
+const SPAWN_DIMENSION: &str = "minecraft:overworld";
+
+// Largest value a 3 byte VarInt packet length can hold
+const MAX_PACKET_LEN: u32 = 2097151;
+
+#[derive(Debug)]
+pub struct Connection {
+    pub id: u32,
+    pub address: SocketAddr,
+    pub connected_at: SystemTime,
+    pub state: State,
+    pub protocol: &'static Protocol,
+    pub position: Option<(f64, f64, f64)>,
+    pub compression_threshold: Option<u32>,
+    pub username: Option<String>,
+    pub uuid: Option<Vec<u8>>,
+    pub properties: Vec<ProfileProperty>,
+    pub verify_token: Vec<u8>,
+    pub shared_secret: Option<Vec<u8>>,
+    pub keep_alive: Option<(u64, Instant)>,
+    pub latency: Option<Duration>,
+}
+
+impl Connection {
+    pub fn new(id: u32, address: SocketAddr) -> Self {
+        Connection {
+            id,
+            address,
+            connected_at: SystemTime::now(),
+            state: State::default(),
+            protocol: protocol::get_default_protocol(),
+            position: None,
+            compression_threshold: None,
+            username: None,
+            uuid: None,
+            properties: vec![],
+            verify_token: vec![],
+            shared_secret: None,
+            keep_alive: None,
+            latency: None,
+        }
+    }
+
+    pub fn info(&self) -> ClientInfo {
+        ClientInfo {
+            id: self.id,
+            address: self.address,
+            username: self.username.clone(),
+            uuid: self.uuid.clone(),
+            properties: self.properties.clone(),
+            state: self.state,
+            position: self.position,
+            latency: self.latency,
+            connected_at: self.connected_at,
+        }
+    }
+}
+
 pub async fn handle_incoming(
-    state: &Mutex<State>,
+    connection: &Mutex<Connection>,
     reader: &mut OwnedReadHalf,
     chan_writer: &Sender<Arc<S2c>>,
 ) -> io::Result<()> {
-    let reader = &mut BufReader::new(reader);
+    let reader = &mut BufReader::new(CipherReader::new(reader));
 
     loop {
         let packet_len = reader.read_var_int().await?;
+        if packet_len > MAX_PACKET_LEN {
+            return Err(io::Error::other("Packet is too big"));
+        }
+
+        let mut frame = vec![0; packet_len as usize];
+        reader.read_exact(&mut frame).await?;
 
-        if packet_len > 0 && reader.buffer().len() > 0 {
-            let mut reader = reader.take(packet_len as u64);
-            let current_state = *state.lock().await;
+        let threshold = connection.lock().await.compression_threshold;
+        let packet = compression::decode_frame(frame, threshold).await?;
+
+        let mut data = &packet[..];
+        handle_packet(connection, &mut data, chan_writer).await?;
+
+        if !data.is_empty() {
+            log::warn!("Packet wasn't fully readed!!!");
+        }
 
-            match handle_packet(current_state, &mut reader, chan_writer).await? {
-                Some(new_state) => {
-                    *state.lock().await = new_state;
+        if !reader.get_ref().is_encrypted() {
+            if let Some(shared_secret) = &connection.lock().await.shared_secret {
+                // Anything already buffered was read before the cipher was enabled
+                if !reader.buffer().is_empty() {
+                    return Err(io::Error::other(
+                        "Unexpected data after Encryption Response",
+                    ));
                 }
-                None => {}
-            };
 
-            if reader.limit() != 0 {
-                log::warn!("Packet wasn't fully readed!!!");
-                reader.consume(reader.limit() as usize);
+                reader
+                    .get_mut()
+                    .set_cipher(crypto::new_decryptor(shared_secret)?);
             }
         }
     }
 }
 
+// Packets the client's own handlers queue and packets from server events come in separately,
+// so a full login queue doesn't count against the events and the other way around
 pub async fn handle_outgoing(
-    state: &Mutex<State>,
+    connection: &Mutex<Connection>,
     connection_writer: &mut OwnedWriteHalf,
     chan_reader: &mut Receiver<Arc<S2c>>,
+    event_reader: &mut Receiver<Arc<S2c>>,
 ) -> io::Result<()> {
-    let mut ticker = tokio::time::interval(Duration::from_secs(10));
+    let mut ticker = tokio::time::interval(KEEP_ALIVE_INTERVAL);
+    let mut compression_threshold = None;
+    let mut cipher = None;
 
     'main: loop {
-        tokio::select! {
+        let packet = tokio::select! {
             _ = ticker.tick() => {
+                let keep_alive_id = {
+                    let mut connection = connection.lock().await;
+
+                    match connection.keep_alive {
+                        _ if connection.state != State::Play => None,
+                        Some((_, sent_at)) if sent_at.elapsed() > get_config().keep_alive_timeout() => {
+
+                            let protocol = connection.protocol;
+                            drop(connection);
+
+                            let reason = TextComponent::text("Timed out");
+                            send_packet(connection_writer, protocol, Arc::new(S2c::Disconnect { reason }), &mut compression_threshold, &mut cipher).await?;
+
+                            return Err(io::Error::new(io::ErrorKind::TimedOut, "Timed out"));
+                        }
+                        Some(_) => None,
+                        None => {
+                            let now = SystemTime::now();
+                            let duration_since_epoch = now.duration_since(SystemTime::UNIX_EPOCH).map_err(io::Error::other)?;
+                            let id = duration_since_epoch.as_nanos() as u64;
 
-                if *state.lock().await == State::Play {
-                    let now = SystemTime::now();
-                    let duration_since_epoch = now.duration_since(SystemTime::UNIX_EPOCH).map_err(io::Error::other)?;
+                            connection.keep_alive = Some((id, Instant::now()));
+                            Some((id, connection.protocol))
+                        }
+                    }
+                };
 
-                    send_packet(connection_writer, Arc::new(S2c::KeepAlive{ id: duration_since_epoch.as_nanos() as u64 })).await?;
+                if let Some((id, protocol)) = keep_alive_id {
+                    send_packet(connection_writer, protocol, Arc::new(S2c::KeepAlive{ id }), &mut compression_threshold, &mut cipher).await?;
                 }
+
+                continue 'main;
             },
             packet = chan_reader.recv() => {
                 match packet {
-                    Some(packet) => { send_packet(connection_writer, packet.clone()).await?; },
+                    Some(packet) => packet,
                     _ => break 'main,
                 }
             },
+            // The server drops the event sender before the client's own one, that's not the end
+            Some(packet) = event_reader.recv() => packet,
+        };
+
+        let protocol = {
+            let connection = connection.lock().await;
+            if cipher.is_none() {
+                if let Some(shared_secret) = &connection.shared_secret {
+                    cipher = Some(crypto::new_encryptor(shared_secret)?);
+                }
+            }
+
+            connection.protocol
         };
+
+        send_packet(
+            connection_writer,
+            protocol,
+            packet,
+            &mut compression_threshold,
+            &mut cipher,
+        )
+        .await?;
     }
 
     Ok(())
 }
 
-async fn send_packet(connection_writer: &mut OwnedWriteHalf, packet: Arc<S2c>) -> io::Result<()> {
+async fn send_packet(
+    connection_writer: &mut OwnedWriteHalf,
+    protocol: &Protocol,
+    packet: Arc<S2c>,
+    compression_threshold: &mut Option<u32>,
+    cipher: &mut Option<Encryptor>,
+) -> io::Result<()> {
     let mut connection_writer = BufWriter::new(connection_writer);
 
     measure!("handle_outgoing[LOOP_ITER]()", {
         let mut buffer = vec![];
-        packet.write_to(&mut buffer).await?;
+        packet.write_to(protocol, &mut buffer).await?;
 
         log::debug!(
             "OUTGOING_PACKET with {} bytes => {:?}",
@@ -89,35 +243,72 @@ async fn send_packet(connection_writer: &mut OwnedWriteHalf, packet: Arc<S2c>) -
             packet
         );
 
-        connection_writer.write_var_int(buffer.len() as u32).await?;
-        connection_writer.write_all(&*buffer).await?;
+        let mut frame = compression::encode_frame(buffer, *compression_threshold).await?;
+        if let Some(cipher) = cipher {
+            crypto::encrypt(cipher, &mut frame);
+        }
+
+        connection_writer.write_all(&frame).await?;
 
         connection_writer.flush().await?;
     });
 
+    // Set Compression itself is sent uncompressed, everything after it is not
+    if let S2c::SetCompression { threshold } = *packet {
+        *compression_threshold = Some(threshold);
+    }
+
     Ok(())
 }
 
 pub async fn handle_packet(
-    state: State,
+    connection: &Mutex<Connection>,
     data: &mut impl AsyncReadOwnExt,
     chan_writer: &Sender<Arc<S2c>>,
-) -> io::Result<Option<State>> {
+) -> io::Result<()> {
+    let (state, protocol) = {
+        let connection = connection.lock().await;
+        (connection.state, connection.protocol)
+    };
     let mut result_state = None;
 
     log::debug!("Reading C2s packet, using state: {:?}", state);
-    match C2s::read(state, data).await? {
-        C2s::Handshake { next_state, .. } => {
-            result_state = Some(State::from_int(next_state).map_err(io::Error::other)?)
+    match C2s::read(protocol, state, data).await? {
+        C2s::Handshake {
+            protocol,
+            next_state,
+            ..
+        } => {
+            let next_state = match State::from_int(next_state) {
+                Ok(next_state @ (State::Status | State::Login)) => next_state,
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Invalid next state {} in handshake", next_state),
+                    ))
+                }
+            };
+
+            let mut connection = connection.lock().await;
+            match protocol::get_protocol(protocol) {
+                Some(protocol) => connection.protocol = protocol,
+                // Status pings still get an answer so the client can show the mismatch
+                None if next_state == State::Login => {
+                    connection.state = State::Login;
+
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        protocol::unsupported_message(protocol),
+                    ));
+                }
+                None => {}
+            }
+
+            result_state = Some(next_state);
         }
         C2s::StatusRequest => {
             let response = S2c::StatusResponse {
-                text: "Powered by mars.rs".to_owned(),
-                version: Version {
-                    name: "Mars".to_owned(),
-                    protocol: 762,
-                },
-                players: Players { online: 5, max: 10 },
+                status: server_status(Some(protocol.version)).await,
             };
 
             S2c::send_to(Arc::new(response), chan_writer).await?;
@@ -130,36 +321,616 @@ pub async fn handle_packet(
         C2s::LoginStart { name, uuid } => {
             let uuid = uuid.unwrap_or(utils::generate_offline_uuid(name.as_str()));
 
-            let response = S2c::LoginSuccess { name, uuid };
-            S2c::send_to(Arc::new(response), chan_writer).await?;
+            // Online mode can't work without the shared secret
+            if get_config().enable_encryption || get_config().online_mode {
+                let verify_token = rand::random::<[u8; 4]>().to_vec();
 
-            let response = S2c::LoginPlay {};
-            S2c::send_to(Arc::new(response), chan_writer).await?;
+                let response = S2c::EncryptionRequest {
+                    server_id: String::new(),
+                    public_key: crypto::get_server_key().public_key_der.clone(),
+                    verify_token: verify_token.clone(),
+                };
+                S2c::send_to(Arc::new(response), chan_writer).await?;
+
+                let mut connection = connection.lock().await;
+                connection.username = Some(name);
+                connection.uuid = Some(uuid);
+                connection.verify_token = verify_token;
+            } else {
+                {
+                    let mut connection = connection.lock().await;
+                    connection.username = Some(name);
+                    connection.uuid = Some(uuid);
+                }
+
+                finish_login(connection, chan_writer).await?;
+                result_state = Some(State::Play);
+            }
+        }
+        C2s::EncryptionResponse {
+            shared_secret,
+            verify_token,
+        } => {
+            let server_key = crypto::get_server_key();
+            let shared_secret = server_key.decrypt(&shared_secret)?;
+            let verify_token = server_key.decrypt(&verify_token)?;
 
-            // Generate demo world
             {
-                let radius = 7 / 2;
-                for x in -radius..radius {
-                    for z in -radius..radius {
-                        let mut world = get_world().lock().await;
-                        let chunk = world.get_chunk(&ChunkPos { x, z });
-                        let response = mapper::map_chunk_to_packet(chunk.unwrap().clone());
-
-                        S2c::send_to(Arc::new(response), chan_writer).await?;
-                    }
+                let mut connection = connection.lock().await;
+                if connection.verify_token.is_empty() || connection.verify_token != verify_token {
+                    return Err(io::Error::other("Verify token doesn't match"));
+                }
+                if shared_secret.len() != 16 {
+                    return Err(io::Error::other("Invalid shared secret length"));
                 }
+
+                connection.shared_secret = Some(shared_secret);
             }
 
-            let response = S2c::SetDefaultSpawnPosition {
-                location: crate::Position { x: 0, y: 50, z: 0 },
-                angle: 0.0,
-            };
-            S2c::send_to(Arc::new(response), chan_writer).await?;
+            if get_config().online_mode {
+                authenticate(connection).await?;
+            }
 
+            finish_login(connection, chan_writer).await?;
             result_state = Some(State::Play);
         }
-        C2s::Mock => {}
+        C2s::ChatMessage { message, .. } => {
+            log::info!("Chat message: {}", message);
+        }
+        C2s::ChatCommand { command, .. } => {
+            log::info!("Issued server command: /{}", command);
+        }
+        C2s::ClientInformation {
+            locale,
+            view_distance,
+            ..
+        } => {
+            log::debug!(
+                "Client information: locale={}, view_distance={}",
+                locale,
+                view_distance
+            );
+        }
+        C2s::SetPlayerPosition { x, y, z, .. }
+        | C2s::SetPlayerPositionAndRotation { x, y, z, .. } => {
+            connection.lock().await.position = Some((x, y, z));
+        }
+        C2s::KeepAlive { id } => {
+            let player = {
+                let mut connection = connection.lock().await;
+
+                match connection.keep_alive {
+                    Some((expected_id, sent_at)) if expected_id == id => {
+                        let latency = sent_at.elapsed();
+                        connection.latency = Some(latency);
+                        connection.keep_alive = None;
+
+                        connection.uuid.clone().map(|uuid| (uuid, latency))
+                    }
+                    _ => return Err(io::Error::other("Unexpected keep alive id")),
+                }
+            };
+
+            if let Some(player) = player {
+                let packet = S2c::PlayerInfoLatency {
+                    players: vec![player],
+                };
+                event::get_event_bus().broadcast(packet).await?;
+            }
+        }
+        C2s::ConfirmTeleportation { .. }
+        | C2s::MessageAcknowledgment { .. }
+        | C2s::ClientCommand { .. }
+        | C2s::CloseContainer { .. }
+        | C2s::PluginMessage { .. }
+        | C2s::SetPlayerRotation { .. }
+        | C2s::SetPlayerOnGround { .. }
+        | C2s::PlayerAbilities { .. }
+        | C2s::PlayerAction { .. }
+        | C2s::PlayerCommand { .. }
+        | C2s::SetHeldItem { .. }
+        | C2s::SwingArm { .. }
+        | C2s::UseItemOn { .. }
+        | C2s::UseItem { .. }
+        | C2s::Mock => {}
+    };
+
+    if let Some(new_state) = result_state {
+        connection.lock().await.state = new_state;
+    }
+
+    Ok(())
+}
+
+// Echoes the client's protocol when we support it, so it isn't shown as incompatible
+pub async fn server_status(client_protocol: Option<u32>) -> Status {
+    let config = get_config();
+    let players = server::get_online_players().await;
+    let protocol = client_protocol
+        .and_then(protocol::get_protocol)
+        .unwrap_or(protocol::get_default_protocol());
+
+    let sample = players
+        .iter()
+        .take(MAX_STATUS_SAMPLE)
+        .filter_map(|player| {
+            let name = player.username.clone()?;
+            let id = uuid::Uuid::from_slice(player.uuid.as_deref()?).ok()?;
+
+            Some(PlayerSample {
+                name,
+                id: id.hyphenated().to_string(),
+                latency: player.latency.map(|latency| latency.as_millis() as u32),
+            })
+        })
+        .collect();
+
+    Status {
+        description: config.motd.clone(),
+        version: Version {
+            name: format!("Mars {}", protocol.name),
+            protocol: protocol.version,
+        },
+        players: Players {
+            max: config.max_players,
+            online: players.len() as u32,
+            sample,
+        },
+        favicon: get_favicon().map(str::to_owned),
+    }
+}
+
+// Loaded once, like vanilla does, from the working directory
+fn get_favicon() -> Option<&'static str> {
+    static FAVICON: OnceLock<Option<String>> = OnceLock::new();
+
+    FAVICON
+        .get_or_init(|| match fs::read(FAVICON_PATH) {
+            Ok(image) => Some(format!(
+                "data:image/png;base64,{}",
+                base64::engine::general_purpose::STANDARD.encode(image)
+            )),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => {
+                log::warn!("Failed to read {}: {}", FAVICON_PATH, e);
+                None
+            }
+        })
+        .as_deref()
+}
+
+async fn authenticate(connection: &Mutex<Connection>) -> io::Result<()> {
+    let (username, server_hash) = {
+        let connection = connection.lock().await;
+
+        match (&connection.username, &connection.shared_secret) {
+            (Some(username), Some(shared_secret)) => (
+                username.clone(),
+                session::server_hash("", shared_secret, &crypto::get_server_key().public_key_der),
+            ),
+            _ => return Err(io::Error::other("Login wasn't started")),
+        }
     };
 
-    Ok(result_state)
+    let profile = session::has_joined(&get_config().session_server, &username, &server_hash)
+        .await?
+        .ok_or_else(|| io::Error::other("Failed to verify username"))?;
+
+    log::info!("Authenticated {} with UUID {}", profile.name, profile.id);
+
+    let mut connection = connection.lock().await;
+    connection.uuid = Some(profile.uuid()?);
+    connection.username = Some(profile.name);
+    connection.properties = profile.properties;
+
+    Ok(())
+}
+
+fn login_play(entity_id: u32, protocol: &Protocol) -> S2c {
+    let config = get_config();
+
+    S2c::LoginPlay {
+        entity_id,
+        is_hardcore: config.hardcore,
+        gamemode: config.gamemode as u8,
+        previous_gamemode: None,
+        dimension_names: DIMENSION_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        registry_codec: registry::get_packed_registry_codec(protocol),
+        dimension_type: SPAWN_DIMENSION.to_owned(),
+        dimension_name: SPAWN_DIMENSION.to_owned(),
+        hashed_seed: utils::hash_seed(config.level_seed),
+        max_players: config.max_players,
+        view_distance: config.view_distance,
+        simulation_distance: config.simulation_distance,
+        reduced_debug_info: config.reduced_debug_info,
+        enable_respawn_screen: config.enable_respawn_screen,
+        is_debug: config.level_type == "minecraft:debug_all_block_states",
+        is_flat: config.level_type == "minecraft:flat",
+        death_location: None,
+        portal_cooldown: 0,
+    }
+}
+
+// Tab list entry of a client, once it has a profile
+fn player_info(info: &ClientInfo) -> Option<PlayerInfo> {
+    Some(PlayerInfo {
+        uuid: info.uuid.clone()?,
+        name: info.username.clone()?,
+        properties: info.properties.clone(),
+        gamemode: get_config().gamemode as u8,
+        latency: info.latency,
+    })
+}
+
+async fn finish_login(
+    connection: &Mutex<Connection>,
+    chan_writer: &Sender<Arc<S2c>>,
+) -> io::Result<()> {
+    let (name, uuid, properties) = {
+        let connection = connection.lock().await;
+
+        match (&connection.username, &connection.uuid) {
+            (Some(name), Some(uuid)) => (name.clone(), uuid.clone(), connection.properties.clone()),
+            _ => return Err(io::Error::other("Login wasn't started")),
+        }
+    };
+
+    if let Some(threshold) = get_config().compression_threshold() {
+        let response = S2c::SetCompression { threshold };
+        S2c::send_to(Arc::new(response), chan_writer).await?;
+
+        connection.lock().await.compression_threshold = Some(threshold);
+    }
+
+    let response = S2c::LoginSuccess {
+        name,
+        uuid,
+        properties,
+    };
+    S2c::send_to(Arc::new(response), chan_writer).await?;
+
+    let (entity_id, protocol, info) = {
+        let connection = connection.lock().await;
+        (connection.id, connection.protocol, connection.info())
+    };
+    S2c::send_to(Arc::new(login_play(entity_id, protocol)), chan_writer).await?;
+
+    // The new player gets everyone already in game and itself, the others just get the new player
+    let own_info = player_info(&info);
+    let mut players: Vec<PlayerInfo> = server::get_online_players()
+        .await
+        .iter()
+        .filter_map(player_info)
+        .collect();
+    players.extend(own_info.clone());
+    S2c::send_to(Arc::new(S2c::PlayerInfoAdd { players }), chan_writer).await?;
+
+    if let Some(own_info) = own_info {
+        let packet = S2c::PlayerInfoAdd {
+            players: vec![own_info],
+        };
+        event::get_event_bus().broadcast(packet).await?;
+    }
+
+    // Generate demo world
+    {
+        let radius = get_config().view_distance as i32;
+        for x in -radius..=radius {
+            for z in -radius..=radius {
+                let mut world = get_world().lock().await;
+                let chunk = world.get_chunk(&ChunkPos { x, z });
+                let response = mapper::map_chunk_to_packet(chunk.unwrap().clone());
+
+                S2c::send_to(Arc::new(response), chan_writer).await?;
+            }
+        }
+    }
+
+    let response = S2c::SetDefaultSpawnPosition {
+        location: crate::Position {
+            x: get_config().spawn_x,
+            y: get_config().spawn_y,
+            z: get_config().spawn_z,
+        },
+        angle: 0.0,
+    };
+    S2c::send_to(Arc::new(response), chan_writer).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tcp::AsyncWriteOwnExt;
+    use crate::VarInt;
+    use rsa::pkcs8::DecodePublicKey;
+    use rsa::{Pkcs1v15Encrypt, RsaPublicKey};
+    use std::net::Ipv4Addr;
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::mpsc;
+
+    const SECRET: [u8; 16] = *b"0123456789abcdef";
+
+    fn connection(state: State) -> Mutex<Connection> {
+        let mut connection = Connection::new(u32::MAX, (Ipv4Addr::LOCALHOST, 25565).into());
+        connection.state = state;
+
+        Mutex::new(connection)
+    }
+
+    // Does what a client does with the key from the encryption request
+    fn client_encrypt(data: &[u8]) -> Vec<u8> {
+        RsaPublicKey::from_public_key_der(&crypto::get_server_key().public_key_der)
+            .unwrap()
+            .encrypt(&mut rand::thread_rng(), Pkcs1v15Encrypt, data)
+            .unwrap()
+    }
+
+    async fn handshake(protocol: VarInt, next_state: VarInt) -> Vec<u8> {
+        let mut packet = vec![];
+        packet.write_var_int(0x00).await.unwrap();
+        packet.write_var_int(protocol).await.unwrap();
+        packet.write_string("localhost").await.unwrap();
+        packet.write_u16(25565).await.unwrap();
+        packet.write_var_int(next_state).await.unwrap();
+        packet
+    }
+
+    async fn login_start(name: &str) -> Vec<u8> {
+        let mut packet = vec![];
+        packet.write_var_int(0x00).await.unwrap();
+        packet.write_string(name).await.unwrap();
+        packet.write_bool(false).await.unwrap();
+        packet
+    }
+
+    async fn encryption_response(shared_secret: &[u8], verify_token: &[u8]) -> Vec<u8> {
+        let mut packet = vec![];
+        packet.write_var_int(0x01).await.unwrap();
+        packet
+            .write_byte_array(&client_encrypt(shared_secret))
+            .await
+            .unwrap();
+        packet
+            .write_byte_array(&client_encrypt(verify_token))
+            .await
+            .unwrap();
+        packet
+    }
+
+    async fn handle(
+        connection: &Mutex<Connection>,
+        packet: &[u8],
+        chan_writer: &Sender<Arc<S2c>>,
+    ) -> io::Result<()> {
+        let mut data = packet;
+        handle_packet(connection, &mut data, chan_writer).await
+    }
+
+    // Sends Login Start and gives back the verify token of the encryption request
+    async fn start_login(
+        connection: &Mutex<Connection>,
+        chan_writer: &Sender<Arc<S2c>>,
+        chan_reader: &mut Receiver<Arc<S2c>>,
+    ) -> Vec<u8> {
+        handle(connection, &login_start("Steve").await, chan_writer)
+            .await
+            .unwrap();
+
+        match &*chan_reader.try_recv().unwrap() {
+            S2c::EncryptionRequest {
+                public_key,
+                verify_token,
+                ..
+            } => {
+                assert_eq!(public_key, &crypto::get_server_key().public_key_der);
+                verify_token.clone()
+            }
+            packet => panic!("Expected an encryption request, got {:?}", packet),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_verify_token() {
+        let connection = connection(State::Login);
+        let (sender, mut receiver) = mpsc::channel(16);
+
+        let verify_token = start_login(&connection, &sender, &mut receiver).await;
+        let wrong_token: Vec<u8> = verify_token.iter().map(|byte| !byte).collect();
+
+        let response = encryption_response(&SECRET, &wrong_token).await;
+        let error = handle(&connection, &response, &sender).await.unwrap_err();
+        assert_eq!(error.to_string(), "Verify token doesn't match");
+
+        let connection = connection.lock().await;
+        assert_eq!(connection.state, State::Login);
+        assert!(connection.shared_secret.is_none());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_response_without_request() {
+        let connection = connection(State::Login);
+        let (sender, _receiver) = mpsc::channel(16);
+
+        let response = encryption_response(&SECRET, &[]).await;
+        assert!(handle(&connection, &response, &sender).await.is_err());
+        assert!(connection.lock().await.shared_secret.is_none());
+    }
+
+    #[tokio::test]
+    async fn rejects_short_shared_secret() {
+        let connection = connection(State::Login);
+        let (sender, mut receiver) = mpsc::channel(16);
+
+        let verify_token = start_login(&connection, &sender, &mut receiver).await;
+
+        let response = encryption_response(&SECRET[..15], &verify_token).await;
+        let error = handle(&connection, &response, &sender).await.unwrap_err();
+        assert_eq!(error.to_string(), "Invalid shared secret length");
+        assert!(connection.lock().await.shared_secret.is_none());
+    }
+
+    async fn socket_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+
+        (client, server)
+    }
+
+    async fn frame(packet: &[u8]) -> Vec<u8> {
+        let mut frame = vec![];
+        frame.write_var_int(packet.len() as VarInt).await.unwrap();
+        frame.extend_from_slice(packet);
+        frame
+    }
+
+    // Waits for the login to finish on the server's side of the channel
+    async fn login_success(chan_reader: &mut Receiver<Arc<S2c>>) {
+        loop {
+            let packet = chan_reader.recv().await.expect("Channel closed");
+            if let S2c::LoginSuccess { name, .. } = &*packet {
+                assert_eq!(name, "Steve");
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn encrypts_after_encryption_response() {
+        event::init_test_event_bus();
+
+        let (mut client, server) = socket_pair().await;
+        let (mut server_reader, _server_writer) = server.into_split();
+        let connection = connection(State::Shake);
+        let (sender, mut receiver) = mpsc::channel(1024);
+
+        let incoming = handle_incoming(&connection, &mut server_reader, &sender);
+        let script = async {
+            client
+                .write_all(&frame(&handshake(763, 2).await).await)
+                .await
+                .unwrap();
+            client
+                .write_all(&frame(&login_start("Steve").await).await)
+                .await
+                .unwrap();
+
+            let verify_token = loop {
+                if let S2c::EncryptionRequest { verify_token, .. } =
+                    &*receiver.recv().await.unwrap()
+                {
+                    break verify_token.clone();
+                }
+            };
+            let response = encryption_response(&SECRET, &verify_token).await;
+            client.write_all(&frame(&response).await).await.unwrap();
+            login_success(&mut receiver).await;
+
+            // From here on frames are compressed, below the threshold that's just a zero
+            let mut packet = vec![0];
+            packet.write_var_int(0x14).await.unwrap();
+            packet.write_f64(1.5).await.unwrap();
+            packet.write_f64(64.0).await.unwrap();
+            packet.write_f64(-2.5).await.unwrap();
+            packet.write_bool(true).await.unwrap();
+
+            let mut frame = frame(&packet).await;
+            crypto::encrypt(&mut crypto::new_encryptor(&SECRET).unwrap(), &mut frame);
+            client.write_all(&frame).await.unwrap();
+
+            // A position only shows up if the server could decrypt it
+            while connection.lock().await.position.is_none() {
+                tokio::task::yield_now().await;
+            }
+        };
+
+        select! {
+            result = incoming => panic!("Connection ended early: {:?}", result),
+            _ = script => {}
+        }
+
+        let connection = connection.lock().await;
+        assert_eq!(connection.state, State::Play);
+        assert_eq!(connection.shared_secret.as_deref(), Some(&SECRET[..]));
+        assert_eq!(connection.position, Some((1.5, 64.0, -2.5)));
+    }
+
+    #[tokio::test]
+    async fn rejects_data_sent_with_encryption_response() {
+        event::init_test_event_bus();
+
+        let (mut client, server) = socket_pair().await;
+        let (mut server_reader, _server_writer) = server.into_split();
+        let connection = connection(State::Login);
+        let (sender, mut receiver) = mpsc::channel(1024);
+
+        let verify_token = start_login(&connection, &sender, &mut receiver).await;
+
+        // The trailing bytes arrive before the server could enable the cipher
+        let mut bytes = frame(&encryption_response(&SECRET, &verify_token).await).await;
+        bytes.extend_from_slice(&[0x02, 0x00, 0x00]);
+        client.write_all(&bytes).await.unwrap();
+
+        let error = handle_incoming(&connection, &mut server_reader, &sender)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unexpected data after Encryption Response"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_login() {
+        let connection = connection(State::Shake);
+        let (sender, mut receiver) = mpsc::channel(16);
+
+        let error = handle(&connection, &handshake(761, 2).await, &sender)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+        assert_eq!(error.to_string(), protocol::unsupported_message(761));
+
+        // Still in login, so the reason goes out as a Login Disconnect
+        let state = connection.lock().await.state;
+        assert_eq!(state, State::Login);
+        assert!(matches!(
+            S2c::disconnect(state, TextComponent::text(&error.to_string())),
+            Some(S2c::LoginDisconnect { .. })
+        ));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    async fn status_protocol(client_protocol: VarInt) -> u32 {
+        let connection = connection(State::Shake);
+        let (sender, mut receiver) = mpsc::channel(16);
+
+        handle(&connection, &handshake(client_protocol, 1).await, &sender)
+            .await
+            .unwrap();
+        assert_eq!(connection.lock().await.state, State::Status);
+
+        handle(&connection, &[0x00], &sender).await.unwrap();
+        match &*receiver.try_recv().unwrap() {
+            S2c::StatusResponse { status } => status.version.protocol,
+            packet => panic!("Expected a status response, got {:?}", packet),
+        }
+    }
+
+    #[tokio::test]
+    async fn answers_status_for_unsupported_versions() {
+        let default = protocol::get_default_protocol().version;
+
+        assert_eq!(status_protocol(761).await, default);
+        assert_eq!(status_protocol(5).await, default);
+        assert_eq!(status_protocol(763).await, 763);
+    }
 }
@@ -0,0 +1,165 @@
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::{self, Read, Write};
+
+use super::{AsyncReadOwnExt, AsyncWriteOwnExt};
+use crate::VarInt;
+
+// Vanilla refuses to inflate packets bigger than this
+const MAX_UNCOMPRESSED_LEN: usize = 8388608;
+
+pub async fn encode_frame(packet: Vec<u8>, threshold: Option<u32>) -> io::Result<Vec<u8>> {
+    let mut frame = vec![];
+
+    match threshold {
+        None => {
+            frame.write_var_int(packet.len() as VarInt).await?;
+            frame.extend_from_slice(&packet);
+        }
+        Some(threshold) => {
+            let mut body = vec![];
+
+            if packet.len() >= threshold as usize {
+                let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+                encoder.write_all(&packet)?;
+
+                body.write_var_int(packet.len() as VarInt).await?;
+                body.extend_from_slice(&encoder.finish()?);
+            } else {
+                body.write_var_int(0).await?;
+                body.extend_from_slice(&packet);
+            }
+
+            frame.write_var_int(body.len() as VarInt).await?;
+            frame.extend_from_slice(&body);
+        }
+    }
+
+    Ok(frame)
+}
+
+pub async fn decode_frame(frame: Vec<u8>, threshold: Option<u32>) -> io::Result<Vec<u8>> {
+    let Some(threshold) = threshold else {
+        return Ok(frame);
+    };
+
+    let mut body = &frame[..];
+    let data_len = body.read_var_int().await? as usize;
+
+    if data_len == 0 {
+        return Ok(body.to_vec());
+    }
+
+    // Anything this small should have been sent uncompressed, vanilla rejects it too
+    if data_len < threshold as usize {
+        return Err(io::Error::other("Compressed packet is below the threshold"));
+    }
+
+    if data_len > MAX_UNCOMPRESSED_LEN {
+        return Err(io::Error::other("Compressed packet is too big"));
+    }
+
+    let mut packet = Vec::with_capacity(data_len);
+    ZlibDecoder::new(body)
+        .take(data_len as u64 + 1)
+        .read_to_end(&mut packet)?;
+
+    if packet.len() != data_len {
+        return Err(io::Error::other(
+            "Decompressed packet length doesn't match the declared one",
+        ));
+    }
+
+    Ok(packet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The frame without the packet length in front, like the reader hands it over
+    async fn round_trip(packet: &[u8], threshold: Option<u32>) -> Vec<u8> {
+        let frame = encode_frame(packet.to_vec(), threshold).await.unwrap();
+
+        let mut reader = &frame[..];
+        let len = reader.read_var_int().await.unwrap();
+        assert_eq!(len as usize, reader.len());
+
+        decode_frame(reader.to_vec(), threshold).await.unwrap()
+    }
+
+    fn compressed(packet: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+        encoder.write_all(packet).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    async fn body(data_len: VarInt, payload: &[u8]) -> Vec<u8> {
+        let mut body = vec![];
+        body.write_var_int(data_len).await.unwrap();
+        body.extend_from_slice(payload);
+        body
+    }
+
+    #[tokio::test]
+    async fn without_threshold_frames_are_plain() {
+        let packet = vec![0x00, 1, 2, 3];
+
+        let frame = encode_frame(packet.clone(), None).await.unwrap();
+        assert_eq!(frame, [4, 0x00, 1, 2, 3]);
+        assert_eq!(round_trip(&packet, None).await, packet);
+    }
+
+    #[tokio::test]
+    async fn small_packets_are_not_compressed() {
+        let packet = vec![0x21, 7, 7, 7];
+
+        let frame = encode_frame(packet.clone(), Some(256)).await.unwrap();
+        assert_eq!(frame, [5, 0, 0x21, 7, 7, 7]);
+        assert_eq!(round_trip(&packet, Some(256)).await, packet);
+    }
+
+    #[tokio::test]
+    async fn large_packets_are_compressed() {
+        let packet = vec![0x24; 1000];
+
+        let frame = encode_frame(packet.clone(), Some(256)).await.unwrap();
+        assert!(frame.len() < packet.len());
+        assert_eq!(round_trip(&packet, Some(256)).await, packet);
+
+        // Exactly the threshold is compressed as well
+        let packet = vec![0x24; 256];
+        let frame = encode_frame(packet.clone(), Some(256)).await.unwrap();
+        assert_ne!(frame[2], 0);
+        assert_eq!(round_trip(&packet, Some(256)).await, packet);
+    }
+
+    #[tokio::test]
+    async fn rejects_length_mismatch() {
+        let packet = vec![1; 300];
+
+        let shorter = body(299, &compressed(&packet)).await;
+        assert!(decode_frame(shorter, Some(256)).await.is_err());
+
+        let longer = body(301, &compressed(&packet)).await;
+        assert!(decode_frame(longer, Some(256)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_packets() {
+        let len = MAX_UNCOMPRESSED_LEN as VarInt + 1;
+        let frame = body(len, &compressed(&[0; 16])).await;
+
+        assert!(decode_frame(frame, Some(256)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_compressed_packets_below_threshold() {
+        let packet = vec![1; 100];
+        let frame = body(100, &compressed(&packet)).await;
+
+        assert!(decode_frame(frame.clone(), Some(256)).await.is_err());
+        assert_eq!(decode_frame(frame, Some(64)).await.unwrap(), packet);
+    }
+}
@@ -0,0 +1,228 @@
+use cfb8::cipher::generic_array::GenericArray;
+use cfb8::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use rsa::pkcs8::EncodePublicKey;
+use rsa::{Pkcs1v15Encrypt, RsaPrivateKey};
+use std::io;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::log;
+
+pub type Encryptor = cfb8::Encryptor<aes::Aes128>;
+pub type Decryptor = cfb8::Decryptor<aes::Aes128>;
+
+pub struct ServerKey {
+    private_key: RsaPrivateKey,
+    pub public_key_der: Vec<u8>,
+}
+
+impl ServerKey {
+    fn generate() -> Self {
+        log::debug!("Generating server key pair...");
+
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 1024)
+            .expect("Failed to generate the server key pair");
+        let public_key_der = private_key
+            .to_public_key()
+            .to_public_key_der()
+            .expect("Failed to encode the server public key")
+            .into_vec();
+
+        ServerKey {
+            private_key,
+            public_key_der,
+        }
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        self.private_key
+            .decrypt(Pkcs1v15Encrypt, data)
+            .map_err(io::Error::other)
+    }
+}
+
+pub fn get_server_key() -> &'static ServerKey {
+    static SERVER_KEY: OnceLock<ServerKey> = OnceLock::new();
+
+    SERVER_KEY.get_or_init(ServerKey::generate)
+}
+
+pub fn new_encryptor(shared_secret: &[u8]) -> io::Result<Encryptor> {
+    Encryptor::new_from_slices(shared_secret, shared_secret).map_err(io::Error::other)
+}
+
+pub fn new_decryptor(shared_secret: &[u8]) -> io::Result<Decryptor> {
+    Decryptor::new_from_slices(shared_secret, shared_secret).map_err(io::Error::other)
+}
+
+pub fn encrypt(cipher: &mut Encryptor, data: &mut [u8]) {
+    for byte in data.chunks_mut(1) {
+        cipher.encrypt_block_mut(GenericArray::from_mut_slice(byte));
+    }
+}
+
+pub fn decrypt(cipher: &mut Decryptor, data: &mut [u8]) {
+    for byte in data.chunks_mut(1) {
+        cipher.decrypt_block_mut(GenericArray::from_mut_slice(byte));
+    }
+}
+
+// Reader that transparently decrypts everything once a cipher has been set
+pub struct CipherReader<R> {
+    inner: R,
+    cipher: Option<Decryptor>,
+}
+
+impl<R> CipherReader<R> {
+    pub fn new(inner: R) -> Self {
+        CipherReader {
+            inner,
+            cipher: None,
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    pub fn set_cipher(&mut self, cipher: Decryptor) {
+        self.cipher = Some(cipher);
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for CipherReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        let this = &mut *self;
+
+        let result = Pin::new(&mut this.inner).poll_read(cx, buf);
+
+        if let Some(cipher) = this.cipher.as_mut() {
+            decrypt(cipher, &mut buf.filled_mut()[filled..]);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsa::pkcs8::DecodePublicKey;
+    use rsa::RsaPublicKey;
+    use tokio::io::AsyncReadExt;
+
+    const SECRET: [u8; 16] = *b"0123456789abcdef";
+
+    // Does what a client does with the key from the encryption request
+    fn client_encrypt(data: &[u8]) -> Vec<u8> {
+        let public_key = RsaPublicKey::from_public_key_der(&get_server_key().public_key_der)
+            .expect("Public key is valid DER");
+
+        public_key
+            .encrypt(&mut rand::thread_rng(), Pkcs1v15Encrypt, data)
+            .unwrap()
+    }
+
+    #[test]
+    fn decrypts_shared_secret_and_verify_token() {
+        let verify_token = [0xde, 0xad, 0xbe, 0xef];
+
+        let key = get_server_key();
+        assert_eq!(key.decrypt(&client_encrypt(&SECRET)).unwrap(), SECRET);
+        assert_eq!(
+            key.decrypt(&client_encrypt(&verify_token)).unwrap(),
+            verify_token
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_secret() {
+        let mut encrypted = client_encrypt(&SECRET);
+        encrypted[0] ^= 0xff;
+
+        assert!(get_server_key().decrypt(&encrypted).is_err());
+    }
+
+    // NIST SP 800-38A, F.3.7 CFB8-AES128.Encrypt
+    #[test]
+    fn matches_cfb8_test_vector() {
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ];
+        let iv: Vec<u8> = (0..16).collect();
+        let plain = [
+            0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93,
+            0x17, 0x2a, 0xae, 0x2d,
+        ];
+        let cipher = [
+            0x3b, 0x79, 0x42, 0x4c, 0x9c, 0x0d, 0xd4, 0x36, 0xba, 0xce, 0x9e, 0x0e, 0xd4, 0x58,
+            0x6a, 0x4f, 0x32, 0xb9,
+        ];
+
+        let mut data = plain;
+        encrypt(
+            &mut Encryptor::new_from_slices(&key, &iv).unwrap(),
+            &mut data,
+        );
+        assert_eq!(data, cipher);
+
+        decrypt(
+            &mut Decryptor::new_from_slices(&key, &iv).unwrap(),
+            &mut data,
+        );
+        assert_eq!(data, plain);
+    }
+
+    // The cipher state carries over between packets, so splitting the stream mustn't matter
+    #[test]
+    fn round_trips_split_stream() {
+        let plain: Vec<u8> = (0..=255).collect();
+
+        let mut encrypted = plain.clone();
+        let mut encryptor = new_encryptor(&SECRET).unwrap();
+        for chunk in encrypted.chunks_mut(7) {
+            encrypt(&mut encryptor, chunk);
+        }
+        assert_ne!(encrypted, plain);
+
+        let mut decryptor = new_decryptor(&SECRET).unwrap();
+        decrypt(&mut decryptor, &mut encrypted);
+        assert_eq!(encrypted, plain);
+    }
+
+    #[test]
+    fn rejects_short_secret() {
+        assert!(new_encryptor(&SECRET[..8]).is_err());
+        assert!(new_decryptor(&SECRET[..8]).is_err());
+    }
+
+    // Client side of the handshake: send the secret, then talk encrypted both ways
+    #[tokio::test]
+    async fn handshake_with_local_client() {
+        let shared_secret = get_server_key().decrypt(&client_encrypt(&SECRET)).unwrap();
+
+        let mut client_encryptor = new_encryptor(&SECRET).unwrap();
+        let mut sent = b"\x05hello".to_vec();
+        encrypt(&mut client_encryptor, &mut sent);
+
+        let mut reader = CipherReader::new(&sent[..]);
+        assert!(!reader.is_encrypted());
+        reader.set_cipher(new_decryptor(&shared_secret).unwrap());
+        let mut received = vec![];
+        reader.read_to_end(&mut received).await.unwrap();
+        assert_eq!(received, b"\x05hello");
+
+        let mut reply = b"\x03bye".to_vec();
+        encrypt(&mut new_encryptor(&shared_secret).unwrap(), &mut reply);
+        decrypt(&mut new_decryptor(&SECRET).unwrap(), &mut reply);
+        assert_eq!(reply, b"\x03bye");
+    }
+}
@@ -1,8 +1,96 @@
-use std::sync::Arc;
+use std::io;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::mpsc::Sender;
 
 use super::packet::S2c;
+use crate::Position;
 
 #[derive(Debug)]
 pub enum Event {
-    BroadcastEvent { packet: Arc<S2c> },
+    Broadcast {
+        packet: Arc<S2c>,
+    },
+    SendTo {
+        client_id: u32,
+        packet: Arc<S2c>,
+    },
+    SendNear {
+        position: Position,
+        radius: f64,
+        packet: Arc<S2c>,
+    },
+    Kick {
+        client_id: u32,
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: Sender<Event>,
+}
+
+impl EventBus {
+    pub async fn send(&self, event: Event) -> io::Result<()> {
+        self.sender.send(event).await.map_err(io::Error::other)
+    }
+
+    pub async fn broadcast(&self, packet: S2c) -> io::Result<()> {
+        self.send(Event::Broadcast {
+            packet: Arc::new(packet),
+        })
+        .await
+    }
+
+    pub async fn send_to(&self, client_id: u32, packet: S2c) -> io::Result<()> {
+        self.send(Event::SendTo {
+            client_id,
+            packet: Arc::new(packet),
+        })
+        .await
+    }
+
+    pub async fn send_near(&self, position: Position, radius: f64, packet: S2c) -> io::Result<()> {
+        self.send(Event::SendNear {
+            position,
+            radius,
+            packet: Arc::new(packet),
+        })
+        .await
+    }
+
+    pub async fn kick(&self, client_id: u32, reason: &str) -> io::Result<()> {
+        self.send(Event::Kick {
+            client_id,
+            reason: reason.to_owned(),
+        })
+        .await
+    }
+}
+
+static EVENT_BUS: OnceLock<EventBus> = OnceLock::new();
+
+pub fn init_event_bus(sender: Sender<Event>) {
+    if EVENT_BUS.set(EventBus { sender }).is_err() {
+        panic!("Event bus was already initialized");
+    }
+}
+
+pub fn get_event_bus() -> &'static EventBus {
+    EVENT_BUS
+        .get()
+        .expect("Event bus used before the server started")
+}
+
+// Tests don't run the server, their events are taken off the bus and dropped
+#[cfg(test)]
+pub fn init_test_event_bus() {
+    static INIT: std::sync::Once = std::sync::Once::new();
+
+    INIT.call_once(|| {
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(16);
+        init_event_bus(sender);
+
+        std::thread::spawn(move || while receiver.blocking_recv().is_some() {});
+    });
 }
@@ -0,0 +1,86 @@
+use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use super::client;
+use crate::log;
+
+// First byte of the pre-Netty server list ping (0xFE 0x01 ...)
+pub const LEGACY_PING: u8 = 0xFE;
+
+// Clients send the whole ping at once, don't wait for more than this
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+pub async fn handle_legacy_ping(mut socket: TcpStream) -> io::Result<()> {
+    // Drain the request so closing the socket doesn't reset the connection
+    let mut request = [0; 256];
+    let _ = tokio::time::timeout(READ_TIMEOUT, socket.read(&mut request)).await;
+
+    let status = client::server_status(None).await;
+    let response = [
+        "§1".to_owned(),
+        status.version.protocol.to_string(),
+        status.version.name,
+        status.description.to_plain(),
+        status.players.online.to_string(),
+        status.players.max.to_string(),
+    ]
+    .join("\0");
+
+    log::debug!("Answering legacy ping with {:?}", response);
+
+    let chars = response.encode_utf16().collect::<Vec<_>>();
+    let mut buffer = vec![0xFF];
+    buffer.extend_from_slice(&(chars.len() as u16).to_be_bytes());
+    for c in chars {
+        buffer.extend_from_slice(&c.to_be_bytes());
+    }
+
+    socket.write_all(&buffer).await?;
+    socket.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::get_config;
+    use crate::tcp::protocol;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn answers_legacy_ping() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (socket, _) = listener.accept().await.unwrap();
+
+        client.write_all(&[LEGACY_PING, 0x01]).await.unwrap();
+        handle_legacy_ping(socket).await.unwrap();
+
+        let mut response = vec![];
+        client.read_to_end(&mut response).await.unwrap();
+
+        assert_eq!(response[0], 0xFF);
+        let len = u16::from_be_bytes([response[1], response[2]]) as usize;
+        assert_eq!(response.len(), 3 + len * 2);
+
+        let chars: Vec<u16> = response[3..]
+            .chunks(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect();
+        let response = String::from_utf16(&chars).unwrap();
+        let fields: Vec<&str> = response.split('\0').collect();
+
+        let protocol = protocol::get_default_protocol();
+        assert_eq!(fields.len(), 6);
+        assert_eq!(fields[0], "§1");
+        assert_eq!(fields[1], protocol.version.to_string());
+        assert_eq!(fields[2], format!("Mars {}", protocol.name));
+        assert_eq!(fields[3], get_config().motd.to_plain());
+        // Other tests may have players online at the same time
+        assert!(fields[4].parse::<u32>().is_ok());
+        assert_eq!(fields[5], get_config().max_players.to_string());
+    }
+}
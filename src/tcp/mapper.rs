@@ -8,7 +8,7 @@ use crate::{
         block, chunk,
         section::{self, ChunkSection},
     },
-    VarInt,
+    registry, VarInt,
 };
 
 use super::packet::{NetworkChunkPos, NetworkChunkSection, PalettedContainer, S2c};
@@ -58,7 +58,7 @@ fn map_chunk_section(chunk_section: &section::ChunkSection) -> NetworkChunkSecti
         bits_per_entry: 0,
         palette: {
             let mut set = HashSet::new();
-            set.insert(55);
+            set.insert(get_biome_id());
 
             set
         },
@@ -79,6 +79,18 @@ fn map_chunk_section(chunk_section: &section::ChunkSection) -> NetworkChunkSecti
     }
 }
 
+// Every chunk is filled with a single biome for now
+const DEFAULT_BIOME: &str = "minecraft:the_void";
+
+fn get_biome_id() -> VarInt {
+    let id = registry::get_registry_codec()
+        .biomes
+        .get_id(DEFAULT_BIOME)
+        .expect("Default biome must be registered");
+
+    id as VarInt
+}
+
 fn generate_data_vec(
     section: &section::ChunkSection,
     bits_per_entry: u8,
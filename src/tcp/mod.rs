@@ -3,11 +3,17 @@ use std::io;
 use std::io::{Read, Write};
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
+pub mod chat;
 pub mod client;
-mod event;
+mod compression;
+mod crypto;
+pub mod event;
+mod legacy;
 mod mapper;
 mod packet;
+pub mod protocol;
 pub mod server;
+mod session;
 mod state;
 mod utils;
 
@@ -40,6 +46,15 @@ pub trait AsyncReadOwnExt: AsyncReadExt + Unpin {
         Ok(String::from_utf8(buffer).unwrap())
     }
 
+    async fn read_byte_array(&mut self) -> io::Result<Vec<u8>> {
+        let len = self.read_var_int().await?;
+        let mut buffer = vec![0; len as usize];
+
+        self.read_exact(&mut buffer).await?;
+
+        Ok(buffer)
+    }
+
     async fn read_bool(&mut self) -> io::Result<bool> {
         Ok(self.read_u8().await? != 0)
     }
@@ -89,6 +104,17 @@ pub trait AsyncWriteOwnExt: AsyncWriteExt + Unpin {
 
         Ok(())
     }
+
+    async fn write_bool(&mut self, value: bool) -> io::Result<()> {
+        self.write_u8(value as u8).await
+    }
+
+    async fn write_byte_array(&mut self, value: &[u8]) -> io::Result<()> {
+        self.write_var_int(value.len() as VarInt).await?;
+        self.write_all(value).await?;
+
+        Ok(())
+    }
 }
 
 impl<T: AsyncWriteExt + Unpin + ?Sized> AsyncWriteOwnExt for T {}
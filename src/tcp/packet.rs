@@ -1,3 +1,4 @@
+use serde::Serialize;
 use tokio::io::Take;
 use tokio::sync::mpsc::Sender;
 
@@ -6,7 +7,11 @@ use crate::{log, nbt, Position, VarInt};
 use std::collections::HashSet;
 use std::io::{self, Write};
 use std::sync::Arc;
+use std::time::Duration;
 
+use super::chat::TextComponent;
+use super::protocol::{Clientbound, Protocol, Serverbound};
+use super::session::ProfileProperty;
 use super::state::State;
 use super::AsyncReadOwnExt;
 
@@ -26,27 +31,134 @@ pub enum C2s {
         name: String,
         uuid: Option<Vec<u8>>,
     },
+    EncryptionResponse {
+        shared_secret: Vec<u8>,
+        verify_token: Vec<u8>,
+    },
+    ConfirmTeleportation {
+        teleport_id: VarInt,
+    },
+    MessageAcknowledgment {
+        message_count: VarInt,
+    },
+    ChatCommand {
+        command: String,
+        timestamp: u64,
+        salt: u64,
+    },
+    ChatMessage {
+        message: String,
+        timestamp: u64,
+        salt: u64,
+        signature: Option<Vec<u8>>,
+    },
+    ClientCommand {
+        action_id: VarInt,
+    },
+    ClientInformation {
+        locale: String,
+        view_distance: u8,
+        chat_mode: VarInt,
+        chat_colors: bool,
+        displayed_skin_parts: u8,
+        main_hand: VarInt,
+        enable_text_filtering: bool,
+        allow_server_listings: bool,
+    },
+    CloseContainer {
+        window_id: u8,
+    },
+    PluginMessage {
+        channel: String,
+        data: Vec<u8>,
+    },
+    KeepAlive {
+        id: u64,
+    },
+    SetPlayerPosition {
+        x: f64,
+        y: f64,
+        z: f64,
+        on_ground: bool,
+    },
+    SetPlayerPositionAndRotation {
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+        on_ground: bool,
+    },
+    SetPlayerRotation {
+        yaw: f32,
+        pitch: f32,
+        on_ground: bool,
+    },
+    SetPlayerOnGround {
+        on_ground: bool,
+    },
+    PlayerAbilities {
+        flags: u8,
+    },
+    PlayerAction {
+        status: VarInt,
+        location: Position,
+        face: u8,
+        sequence: VarInt,
+    },
+    PlayerCommand {
+        entity_id: VarInt,
+        action_id: VarInt,
+        jump_boost: VarInt,
+    },
+    SetHeldItem {
+        slot: i16,
+    },
+    SwingArm {
+        hand: VarInt,
+    },
+    UseItemOn {
+        hand: VarInt,
+        location: Position,
+        face: VarInt,
+        cursor_x: f32,
+        cursor_y: f32,
+        cursor_z: f32,
+        inside_block: bool,
+        sequence: VarInt,
+    },
+    UseItem {
+        hand: VarInt,
+        sequence: VarInt,
+    },
     Mock,
 }
 
 impl C2s {
-    pub async fn read(state: State, reader: &mut impl AsyncReadOwnExt) -> io::Result<Self> {
+    pub async fn read(
+        protocol: &Protocol,
+        state: State,
+        reader: &mut impl AsyncReadOwnExt,
+    ) -> io::Result<Self> {
         let packet_id = reader.read_var_int().await?;
         log::debug!(
-            "C2s::read(state={:?}, packet_id={}) => INIT ",
+            "C2s::read(protocol={}, state={:?}, packet_id={}) => INIT ",
+            protocol.version,
             state,
             packet_id
         );
 
-        match state {
-            State::Shake => Self::read_shake_state(packet_id, reader).await,
-            State::Status => Self::read_status_state(packet_id, reader).await,
-            State::Login => Self::read_login_state(packet_id, reader).await,
-            State::Play => Ok(Self::Mock), //Self::read_play_state(packet_id, reader).await,
-        }
-        .inspect(|packet| {
+        let packet = match protocol.serverbound(state, packet_id) {
+            Some(packet) => Self::read_packet(packet, reader).await,
+            // Packets we don't handle yet are skipped by the caller
+            None if state == State::Play => Ok(Self::Mock),
+            None => Err(invalid_packet_id(state, packet_id)),
+        };
+
+        packet.inspect(|packet| {
             log::debug!(
-                "C2s::read(state={:?}, packet_id={}) => {:?}",
+                "C2s::read(protocol={}, state={:?}, packet_id={}) => {:?}",
+                protocol.version,
                 state,
                 packet_id,
                 packet
@@ -54,40 +166,22 @@ impl C2s {
         })
     }
 
-    async fn read_shake_state(
-        packet_id: VarInt,
+    async fn read_packet(
+        packet: Serverbound,
         reader: &mut impl AsyncReadOwnExt,
     ) -> io::Result<Self> {
-        match packet_id {
-            0x00 => Ok(Self::Handshake {
+        match packet {
+            Serverbound::Handshake => Ok(Self::Handshake {
                 protocol: reader.read_var_int().await?,
                 address: reader.read_string().await?,
                 port: reader.read_u16().await?,
                 next_state: reader.read_var_int().await?,
             }),
-            _ => Err(io::Error::other("Invalid packet_id for current state")),
-        }
-    }
-
-    async fn read_status_state(
-        packet_id: VarInt,
-        reader: &mut impl AsyncReadOwnExt,
-    ) -> io::Result<Self> {
-        match packet_id {
-            0x00 => Ok(Self::StatusRequest),
-            0x01 => Ok(Self::PingRequest {
+            Serverbound::StatusRequest => Ok(Self::StatusRequest),
+            Serverbound::PingRequest => Ok(Self::PingRequest {
                 timestamp: reader.read_u64().await?,
             }),
-            _ => Err(io::Error::other("Invalid packet_id for current state")),
-        }
-    }
-
-    async fn read_login_state(
-        packet_id: VarInt,
-        reader: &mut impl AsyncReadOwnExt,
-    ) -> io::Result<Self> {
-        match packet_id {
-            0x00 => {
+            Serverbound::LoginStart => {
                 let name = reader.read_string().await?;
                 let (has_uuid, uuid) = reader.read_uuid().await?;
 
@@ -96,70 +190,241 @@ impl C2s {
                     uuid: has_uuid.then_some(uuid),
                 })
             }
-            _ => Err(io::Error::other("Invalid packet_id for current state")),
-        }
-    }
+            Serverbound::EncryptionResponse => Ok(Self::EncryptionResponse {
+                shared_secret: reader.read_byte_array().await?,
+                verify_token: reader.read_byte_array().await?,
+            }),
+            Serverbound::ConfirmTeleportation => Ok(Self::ConfirmTeleportation {
+                teleport_id: reader.read_var_int().await?,
+            }),
+            Serverbound::MessageAcknowledgment => Ok(Self::MessageAcknowledgment {
+                message_count: reader.read_var_int().await?,
+            }),
+            Serverbound::ChatCommand => {
+                let command = reader.read_string().await?;
+                let timestamp = reader.read_u64().await?;
+                let salt = reader.read_u64().await?;
 
-    async fn read_play_state(
-        packet_id: VarInt,
-        reader: &mut impl AsyncReadOwnExt,
-    ) -> io::Result<Self> {
-        match packet_id {
-            _ => Err(io::Error::other("Invalid packet_id for current state")),
+                // Argument signatures, last seen messages and the acknowledged
+                // bitset are only needed for secure chat, which we don't enforce.
+                let signatures = reader.read_var_int().await?;
+                for _ in 0..signatures {
+                    reader.read_string().await?;
+                    reader.read_exact(&mut [0; 256]).await?;
+                }
+                reader.read_var_int().await?;
+                reader.read_exact(&mut [0; 3]).await?;
+
+                Ok(Self::ChatCommand {
+                    command,
+                    timestamp,
+                    salt,
+                })
+            }
+            Serverbound::ChatMessage => {
+                let message = reader.read_string().await?;
+                let timestamp = reader.read_u64().await?;
+                let salt = reader.read_u64().await?;
+                let signature = if reader.read_bool().await? {
+                    let mut buffer = vec![0; 256];
+                    reader.read_exact(&mut buffer).await?;
+
+                    Some(buffer)
+                } else {
+                    None
+                };
+                reader.read_var_int().await?;
+                reader.read_exact(&mut [0; 3]).await?;
+
+                Ok(Self::ChatMessage {
+                    message,
+                    timestamp,
+                    salt,
+                    signature,
+                })
+            }
+            Serverbound::ClientCommand => Ok(Self::ClientCommand {
+                action_id: reader.read_var_int().await?,
+            }),
+            Serverbound::ClientInformation => Ok(Self::ClientInformation {
+                locale: reader.read_string().await?,
+                view_distance: reader.read_u8().await?,
+                chat_mode: reader.read_var_int().await?,
+                chat_colors: reader.read_bool().await?,
+                displayed_skin_parts: reader.read_u8().await?,
+                main_hand: reader.read_var_int().await?,
+                enable_text_filtering: reader.read_bool().await?,
+                allow_server_listings: reader.read_bool().await?,
+            }),
+            Serverbound::CloseContainer => Ok(Self::CloseContainer {
+                window_id: reader.read_u8().await?,
+            }),
+            Serverbound::PluginMessage => {
+                let channel = reader.read_string().await?;
+                let mut data = vec![];
+                reader.read_to_end(&mut data).await?;
+
+                Ok(Self::PluginMessage { channel, data })
+            }
+            Serverbound::KeepAlive => Ok(Self::KeepAlive {
+                id: reader.read_u64().await?,
+            }),
+            Serverbound::SetPlayerPosition => Ok(Self::SetPlayerPosition {
+                x: reader.read_f64().await?,
+                y: reader.read_f64().await?,
+                z: reader.read_f64().await?,
+                on_ground: reader.read_bool().await?,
+            }),
+            Serverbound::SetPlayerPositionAndRotation => Ok(Self::SetPlayerPositionAndRotation {
+                x: reader.read_f64().await?,
+                y: reader.read_f64().await?,
+                z: reader.read_f64().await?,
+                yaw: reader.read_f32().await?,
+                pitch: reader.read_f32().await?,
+                on_ground: reader.read_bool().await?,
+            }),
+            Serverbound::SetPlayerRotation => Ok(Self::SetPlayerRotation {
+                yaw: reader.read_f32().await?,
+                pitch: reader.read_f32().await?,
+                on_ground: reader.read_bool().await?,
+            }),
+            Serverbound::SetPlayerOnGround => Ok(Self::SetPlayerOnGround {
+                on_ground: reader.read_bool().await?,
+            }),
+            Serverbound::PlayerAbilities => Ok(Self::PlayerAbilities {
+                flags: reader.read_u8().await?,
+            }),
+            Serverbound::PlayerAction => Ok(Self::PlayerAction {
+                status: reader.read_var_int().await?,
+                location: Position::read_from(reader).await?,
+                face: reader.read_u8().await?,
+                sequence: reader.read_var_int().await?,
+            }),
+            Serverbound::PlayerCommand => Ok(Self::PlayerCommand {
+                entity_id: reader.read_var_int().await?,
+                action_id: reader.read_var_int().await?,
+                jump_boost: reader.read_var_int().await?,
+            }),
+            Serverbound::SetHeldItem => Ok(Self::SetHeldItem {
+                slot: reader.read_i16().await?,
+            }),
+            Serverbound::SwingArm => Ok(Self::SwingArm {
+                hand: reader.read_var_int().await?,
+            }),
+            Serverbound::UseItemOn => Ok(Self::UseItemOn {
+                hand: reader.read_var_int().await?,
+                location: Position::read_from(reader).await?,
+                face: reader.read_var_int().await?,
+                cursor_x: reader.read_f32().await?,
+                cursor_y: reader.read_f32().await?,
+                cursor_z: reader.read_f32().await?,
+                inside_block: reader.read_bool().await?,
+                sequence: reader.read_var_int().await?,
+            }),
+            Serverbound::UseItem => Ok(Self::UseItem {
+                hand: reader.read_var_int().await?,
+                sequence: reader.read_var_int().await?,
+            }),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+fn invalid_packet_id(state: State, packet_id: VarInt) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Unknown packet id {:#04x} in {:?} state", packet_id, state),
+    )
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Status {
+    pub description: TextComponent,
+    pub version: Version,
+    pub players: Players,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Version {
     pub name: String,
     pub protocol: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Players {
-    pub online: u32,
     pub max: u32,
+    pub online: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sample: Vec<PlayerSample>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerSample {
+    pub name: String,
+    pub id: String,
+    // Milliseconds, the vanilla client ignores it but server lists can show it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency: Option<u32>,
+}
+
+// One tab list entry
+#[derive(Debug, Clone)]
+pub struct PlayerInfo {
+    pub uuid: Vec<u8>,
+    pub name: String,
+    pub properties: Vec<ProfileProperty>,
+    pub gamemode: u8,
+    pub latency: Option<Duration>,
+}
+
+// Player Info Update actions, every entry carries the fields of each action that is set
+const ADD_PLAYER: u8 = 0x01;
+const UPDATE_GAME_MODE: u8 = 0x04;
+const UPDATE_LISTED: u8 = 0x08;
+const UPDATE_LATENCY: u8 = 0x10;
+
 #[derive(Debug, Clone)]
 pub enum S2c {
     StatusResponse {
-        text: String,
-        version: Version,
-        players: Players,
+        status: Status,
     },
     PongResponse {
         timestamp: u64,
     },
+    EncryptionRequest {
+        server_id: String,
+        public_key: Vec<u8>,
+        verify_token: Vec<u8>,
+    },
+    SetCompression {
+        threshold: u32,
+    },
     LoginSuccess {
         name: String,
         uuid: Vec<u8>,
+        properties: Vec<ProfileProperty>,
+    },
+    LoginPlay {
+        entity_id: u32,
+        is_hardcore: bool,
+        gamemode: u8,
+        previous_gamemode: Option<u8>,
+        dimension_names: Vec<String>,
+        registry_codec: &'static [u8],
+        dimension_type: String,
+        dimension_name: String,
+        hashed_seed: u64,
+        max_players: VarInt,
+        view_distance: VarInt,
+        simulation_distance: VarInt,
+        reduced_debug_info: bool,
+        enable_respawn_screen: bool,
+        is_debug: bool,
+        is_flat: bool,
+        death_location: Option<(String, Position)>,
+        portal_cooldown: VarInt,
     },
-    /*
-       LoginPlay {
-           entity_id: u32,
-           is_hardcore: bool,
-           gamemode: u8,
-           previous_gamemode: u8,
-           dimensions_names: Vec<String>,
-           //registry_codec: NbtCompound
-           dimension_type: String,
-           dimension_name: String,
-           hashed_seed: u64,
-           max_players: VarInt,
-           view_distance: VarInt,
-           simulation_distance: VarInt,
-           reduced_debug_info: bool,
-           enable_respawn_screen: bool,
-           is_debug: bool,
-           is_flat: bool,
-           has_dead_location: bool,
-           death_dimension_name: String,
-           death_location: Position,
-       },
-    */
-    LoginPlay {},
     ChunkDataAndLight {
         position: NetworkChunkPos,
         sections: Vec<NetworkChunkSection>,
@@ -171,41 +436,126 @@ pub enum S2c {
     KeepAlive {
         id: u64,
     },
+    PlayerInfoAdd {
+        players: Vec<PlayerInfo>,
+    },
+    PlayerInfoLatency {
+        players: Vec<(Vec<u8>, Duration)>,
+    },
+    PlayerInfoRemove {
+        uuids: Vec<Vec<u8>>,
+    },
+    LoginDisconnect {
+        reason: TextComponent,
+    },
+    Disconnect {
+        reason: TextComponent,
+    },
 }
 
 impl S2c {
-    pub async fn write_to(&self, writer: &mut impl AsyncWriteOwnExt) -> io::Result<()> {
+    pub async fn write_to(
+        &self,
+        protocol: &Protocol,
+        writer: &mut impl AsyncWriteOwnExt,
+    ) -> io::Result<()> {
+        writer
+            .write_var_int(protocol.clientbound_id(self.packet())?)
+            .await?;
+
         match &self {
-            Self::StatusResponse {
-                text,
-                version,
-                players,
-            } => {
-                writer.write_var_int(0x00).await?;
+            Self::StatusResponse { status } => {
                 writer
-                    .write_string(status_to_json(text, version, players).as_ref())
+                    .write_string(&serde_json::to_string(status).map_err(io::Error::other)?)
                     .await?;
             }
             Self::PongResponse { timestamp } => {
-                writer.write_var_int(0x01).await?;
                 writer.write_u64(*timestamp).await?;
             }
-            Self::LoginSuccess { name, uuid } => {
-                writer.write_var_int(0x02).await?;
+            Self::EncryptionRequest {
+                server_id,
+                public_key,
+                verify_token,
+            } => {
+                writer.write_string(server_id).await?;
+                writer.write_byte_array(public_key).await?;
+                writer.write_byte_array(verify_token).await?;
+            }
+            Self::SetCompression { threshold } => {
+                writer.write_var_int(*threshold).await?;
+            }
+            Self::LoginSuccess {
+                name,
+                uuid,
+                properties,
+            } => {
                 writer.write(uuid).await?;
                 writer.write_string(name).await?;
-                writer.write_u8(0x00).await?;
+                write_properties(properties, writer).await?;
             }
-            Self::LoginPlay { .. } => {
-                writer.write(get_stored_packet_bytes()).await?;
+            Self::LoginPlay {
+                entity_id,
+                is_hardcore,
+                gamemode,
+                previous_gamemode,
+                dimension_names,
+                registry_codec,
+                dimension_type,
+                dimension_name,
+                hashed_seed,
+                max_players,
+                view_distance,
+                simulation_distance,
+                reduced_debug_info,
+                enable_respawn_screen,
+                is_debug,
+                is_flat,
+                death_location,
+                portal_cooldown,
+            } => {
+                writer.write_u32(*entity_id).await?;
+                writer.write_bool(*is_hardcore).await?;
+                writer.write_u8(*gamemode).await?;
+                // -1 means there's no previous gamemode
+                writer.write_u8(previous_gamemode.unwrap_or(0xFF)).await?;
+
+                writer
+                    .write_var_int(dimension_names.len() as VarInt)
+                    .await?;
+                for dimension_name in dimension_names {
+                    writer.write_string(dimension_name).await?;
+                }
+
+                writer.write_all(registry_codec).await?;
+                writer.write_string(dimension_type).await?;
+                writer.write_string(dimension_name).await?;
+                writer.write_u64(*hashed_seed).await?;
+                writer.write_var_int(*max_players).await?;
+                writer.write_var_int(*view_distance).await?;
+                writer.write_var_int(*simulation_distance).await?;
+                writer.write_bool(*reduced_debug_info).await?;
+                writer.write_bool(*enable_respawn_screen).await?;
+                writer.write_bool(*is_debug).await?;
+                writer.write_bool(*is_flat).await?;
+
+                writer.write_bool(death_location.is_some()).await?;
+                if let Some((dimension_name, location)) = death_location {
+                    writer.write_string(dimension_name).await?;
+                    location.write_to(writer).await?;
+                }
+
+                if protocol.login_portal_cooldown {
+                    writer.write_var_int(*portal_cooldown).await?;
+                }
             }
             Self::ChunkDataAndLight { position, sections } => {
-                writer.write_var_int(0x24).await?;
                 position.write_to(writer).await?;
 
                 let mut heighmap = nbt::NbtCompound::default();
                 heighmap.set_long_array("MOTION_BLOCKING", vec![0; 37]);
-                writer.write_all(&heighmap.pack().unwrap()).await?;
+                writer
+                    .write_all(&heighmap.pack_as(protocol.nbt_format).unwrap())
+                    .await?;
 
                 let mut section_buffer = vec![];
                 for section in sections {
@@ -216,7 +566,9 @@ impl S2c {
                 writer.write_all(&mut section_buffer).await?;
 
                 writer.write_var_int(0).await?;
-                writer.write_u8(1).await?;
+                if protocol.light_trust_edges {
+                    writer.write_u8(1).await?;
+                }
 
                 writer.write_var_int(0).await?;
                 writer.write_var_int(0).await?;
@@ -227,41 +579,111 @@ impl S2c {
                 writer.write_var_int(0).await?;
             }
             Self::SetDefaultSpawnPosition { location, angle } => {
-                writer.write_var_int(0x50).await?;
                 location.write_to(writer).await?;
 
                 writer.write_f32(*angle).await?;
             }
             S2c::KeepAlive { id } => {
-                writer.write_var_int(0x23).await?;
                 writer.write_u64(*id).await?;
             }
+            S2c::PlayerInfoAdd { players } => {
+                writer
+                    .write_u8(ADD_PLAYER | UPDATE_GAME_MODE | UPDATE_LISTED | UPDATE_LATENCY)
+                    .await?;
+                writer.write_var_int(players.len() as VarInt).await?;
+
+                for player in players {
+                    writer.write_all(&player.uuid).await?;
+                    writer.write_string(&player.name).await?;
+                    write_properties(&player.properties, writer).await?;
+                    writer.write_var_int(player.gamemode as VarInt).await?;
+                    writer.write_bool(true).await?;
+                    // Unknown latency shows as a full signal, like vanilla before the first keep alive
+                    writer
+                        .write_var_int(
+                            player
+                                .latency
+                                .map_or(0, |latency| latency.as_millis() as VarInt),
+                        )
+                        .await?;
+                }
+            }
+            S2c::PlayerInfoLatency { players } => {
+                writer.write_u8(UPDATE_LATENCY).await?;
+                writer.write_var_int(players.len() as VarInt).await?;
+
+                for (uuid, latency) in players {
+                    writer.write_all(uuid).await?;
+                    writer.write_var_int(latency.as_millis() as VarInt).await?;
+                }
+            }
+            S2c::PlayerInfoRemove { uuids } => {
+                writer.write_var_int(uuids.len() as VarInt).await?;
+
+                for uuid in uuids {
+                    writer.write_all(uuid).await?;
+                }
+            }
+            S2c::LoginDisconnect { reason } => {
+                writer.write_string(&reason.to_json()).await?;
+            }
+            S2c::Disconnect { reason } => {
+                writer.write_string(&reason.to_json()).await?;
+            }
         }
 
         Ok(())
     }
 
-    pub async fn send_to(value: Arc<Self>, chan: &Sender<Arc<S2c>>) -> io::Result<()> {
-        chan.send(value).await.map_err(io::Error::other)
+    pub fn packet(&self) -> Clientbound {
+        match self {
+            Self::StatusResponse { .. } => Clientbound::StatusResponse,
+            Self::PongResponse { .. } => Clientbound::PongResponse,
+            Self::EncryptionRequest { .. } => Clientbound::EncryptionRequest,
+            Self::SetCompression { .. } => Clientbound::SetCompression,
+            Self::LoginSuccess { .. } => Clientbound::LoginSuccess,
+            Self::LoginPlay { .. } => Clientbound::LoginPlay,
+            Self::ChunkDataAndLight { .. } => Clientbound::ChunkDataAndLight,
+            Self::SetDefaultSpawnPosition { .. } => Clientbound::SetDefaultSpawnPosition,
+            Self::KeepAlive { .. } => Clientbound::KeepAlive,
+            Self::PlayerInfoAdd { .. } | Self::PlayerInfoLatency { .. } => {
+                Clientbound::PlayerInfoUpdate
+            }
+            Self::PlayerInfoRemove { .. } => Clientbound::PlayerInfoRemove,
+            Self::LoginDisconnect { .. } => Clientbound::LoginDisconnect,
+            Self::Disconnect { .. } => Clientbound::Disconnect,
+        }
     }
-}
-
-fn status_to_json<'a>(text: &'a str, version: &Version, players: &Players) -> String {
-    let image = r#"data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAEAAAABACAYAAACqaXHeAAABhWlDQ1BJQ0MgcHJvZmlsZQAAKJF9kj1Iw0AYht+mSotUHewg4pChOlkoKuKoVShChVArtOpgcukfNDEkKS6OgmvBwZ/FqoOLs64OroIg+APi5uak6CIlfpcUWsR4cNzDe/e+fPfdAUKjyjSrKwFoum1mUkkxl18RQ68Iow9ADxIys4xZSUrDd3zdI8DXuzjP8j/35+hVCxYDAiLxDDNMm3ideGrTNjjvE0dZWVaJz4nHTCqQ+JHrisdvnEsuCzwzamYzc8RRYrHUwUoHs7KpEU8Sx1RNp3wh57HKeYuzVq2xVp38hpGCvrzEdZrDSGEBi5AgQkENFVRhI06rToqFDO0nffxDrl8il0KuChg55rEBDbLrB3+D3721ihPjXlIkCXS/OM7HCBDaBZp1x/k+dpzmCRB8Bq70tn+jAUx/kl5va7EjoH8buLhua8oecLkDDD4Zsim7UpCmUCwC72f0THlg4JY+xKrXt9Y+Th+ALPUqfQMcHAKjJcpe87l3uLNv/55p9e8HNF5yjkyWsEQAAAAGYktHRAD/AP8A/6C9p5MAAAAJcEhZcwAACxMAAAsTAQCanBgAAAAHdElNRQflCA4AHw5wED/6AAAaJElEQVR42u2bSbNs2VXff2vvfbo82dzu3ddVX0INxtUIcPg7SA4jz2zGNl/AM3AUZcPM4RARJvwBPHAzEEaWxoQjHGBjsErISEglq7rX3HebzJvtaXazPDhZT1WoJF6BAiHCZ3AjbkRmnr3/e63/+q9mW35Cz0t3jl+9PSn/+c3J5OLRenf2k1qH/HW/8OU7x6+I6usCnxPUKkQVvgry2hsP5m/8rQXg5TtHr4ryGqKfN2BHVUGRW9o+sWtaUKJivgK8/sbDq6/9rQHgpTsnn7WafhX0C8aIqCqoUOSWepSxbjr6PuIAMYKPqGJ+Jwm/+ScPrv73Ty0AL985flXQXxPlH+UmUo9yTJaDKiOr5CZSOKEPiSSWXBSRRJccZ9eBXR8A+VIUfuNPHsy/9lMDwMt3jl8Bfd2ofn5SOvMzd6f07RpNyqSecDguKB1oSqQIxgoi0MdESkqZOWKCe9cd376/ZNunlES+kjCvfePB5Rt/YwF4n9wQ/Vxm1N49qHj1xZtMCwgxEZNyPCkxKIpgAEFpg7JpezJnAfBJ6b0nxcS2V751b8HDlcfHgSwVee3rP0aytD+GjX/29rj6t0b0iwKfFjA3ZzkHhdB2DWXusEbJjFBllkmVU2WOPDMIwrbtUIXMGZwZYDHG4X3kYrkGiRhj2bTJCHxKML9ya1K9dHNafffRunn4EwPgpTsnr94eV78tol+0xM8kRAAMShdBrePGYU3pLIttS+cTx7OaqPDmwwVNSNRlwaqNnC+3zOqKCNy7XLFpGlzm8DjuXQeutoGUEiAIiCF9BviVm9PqpdNp9eb5uj37a3OBl28fv4LorwvpH8zK3Py9n7nJ3cOS/3ux5X9++yExQFZYyjIjpYj2gcPakjvDsg1oguPasekCqzYxKQ2T0nK5DhgjHFSOLiSudgFrc6wzbFuP9wFnhKORZVY5Nn1gvlM6H1LC/leFX/+Tv4RryMfycfR1VD83LjL7d5875IWTmiqDlODti2vO5jsud4mrjSeiGFUmleNwZMidYdsrT5/MePqoYNkG7l+1PH1UMq4c71613L9cUefQh8SyheUuoIAT+PRTBzx7VJJSZNN23L1xwLQq+d7Fjj/+3iWLTRcR+WrCvPb1j0GW8hdv/ORV0fQvBP2HmVXzyVszXrxZk5uEc5aYEiEmdl3gcuO5vwr0amiajpeeOeT2xNL1AZdZbh+OsaK0bU+WZ2TO4kNk13qsEaIarrcNmTUURcbDVeQbb59zoxZGmXD7cEqVD15bZo7DyYhpmeHV8Idvz/n9bz0gREmK/m4S/tXXHyy+9pfmgJfvHL96e1L9tiF9UeAzo9zIc8cFjpYyc1R5RoxhWEyecedwzNMnM1xe8vb5iqbx9N7zzMmYg3HBKM/RBLvWs+s6eh9JwLbp6H1AxDKqck4mJePSsesSf/S9S65WLbkzPHs643ha4qwhpUiIiXXT0kflYt1w7+EZ01HOLkTpE5+xKr9yOh29dHMyevPRujl7YgBeun38yu1J9e8E/TfG8LNVmQFK5xOQePpkxs2jmtwKVZGT5xliDX1MXG4833j3EhWLiPD3P3WLWQFWBGMM612D35NZUhlAUHDWkGc5MSmd9zS9R4xwOBlzuW44rh3R95zMxmiMxKSAYI0FEYwk+iS8fbVj3USsM5RFRorxM6L6z25NRq/cnFbf/iggPgTAp04P/2Um+u9F+FkBqauCUeVw1mIEbk4txMDpbMyN6YjMDRtt246mG5g6Yjlftuwaz/l8SVVmnExHjHKLkWHTAMYasjxDVfduFPEh0PlIjIm2hzfevmK+acmt8DN3TjidVeRZRusjqoqqkpIy37TMV1uyzNJEGJUFRe5QEbyPgvDppPJPx0XuFk33ex/cs/vgP0n1+TYm64zBGWHTdOxaQZOCCGdrw6svnHB6OKF2QtREu27ow7Cppg8smx7nDHlmqSrharmltHAyq/F9T54Z6qpEAB8jh3VNbuBq3dD0gcxZVBNeldPDEdvOM8oMZ4slop7CffDMFNVEXhTEPPHgfEfTKU3bIkaICVSFxnualKzC83/eAtyH/1UU8CkRElgDzhgMghWDiuU7DxacX17xmbtHPH9jjHMWayw+Jow1VLkjrXuSKiEqGItzFtXBDayxOCPUuaV0BYiAKHUxofPK1bbh4WLLvasND9c9fYQ+Qr63tsNxybQu2TQ9F8st5+uW87Vn5Q1GBCNC1EFRdj7SxiGSqLz/50cCMDx55ggxEpKiKWHEkAistxHfCm7iWLaBR2uPpkjnI+erLYttzyZYVCGmRIxC10fePb/GmmsOJzVGDNfvPuKp02OeOpmRQkfTe8ZVhTJ8r8wMiCPFQIqRKIZ6VHHraEydO4wIJ9OS2ahE3Y53Lu6zWAe6pKRkaGKgC8PGBSFzlj74jwx67gfjolLklpFxLNc7EoOp+6g4ERTDvWvPJqx4GctJ7TAGiqKgWwd8EkwmSG9Y7ALLJjIbGcaF488eXaOaOJ0WvPWdc7ZvnPHJp2ZMRwV/+s4jxMBLzxzwaNXy5sMNKQ2utI2Od+YtVVHR91seXl1x5/iIalTz+29ecH4diQpNiLSxR98P8KqMqhyS0oePjvgfaQExJqxxCMKkcpRWWTSJPiopeYwxXG2U//bNR8wqyzM3Jty/WrPYRorCkhRCHymc4cU7h1yuG1o1nB7kiBWaPlIUGTcPMi63PWfrnoNJgSblW2cbBOWF0xnvXqzoQ2DTCLvW8+aDJYUVZiPHnz06pw+CBzof6GIgAYVR6sLSRcPWewCC6g/VAT8AgDK8LPrItHJYUaal8NTxmD5a3jrfsPGeFCLWKPMdLN9ZwuBptK3HiXD3KOcXP3GLWeHY+jGLTcO0cICw7nrK3JGL4rWi7QLTwpEElm2gygyFNTx/WvGn7855tPR0gKjQBmhXgajQBk+TIqgyLhyfunOIIXF2tSSkyDhztJ0npI8BAIC1lqSB07qgKizWQCZwPHHcGB/yaN3y5tmGPiZCUqwxZGZIccFwNHVMc+X+owvMjQPQyKw0WDOANC0No8wyrQuW245CFGctGOFwb7oxJRbXa2YVBC04X3aDdarShEgfIkkgE+WTdw65OSuoC8PVqqUuC8o80faRB+uIMUL6ISB8JACjMiM3lip3ZGZgXmsHH7IxcTrO6A4d103iahsJKRETODFYC49WnvUOfu6ZCX2CbdOjqtyYjZgUGbNRzijLB51vLfNNgxFHGkIxMQQQg2Qj3no4p/WQVGiCp41xIDeBWWmYZIYb4wwjQuOHnPR4XDDfteTqqEuISdh2/ZMDIEScFU4OajIRrB3Co3MWTUrbeSwwyoSrPdcqEHTICxyWbbL84ffmVA6Oa0tdON666vjU3RMmdcH95Za3zubUVcaoLLi6vqYLntl4xNUm8WcPV6x2/jG5dfF9Vn/fVaFwgpWED4m298SUhpqCCKPMMSosizYQk6JDtH0SAJRZBrlVrCRy5xiPcro+4lPCiFAVGbePppyvW+R6WBjApHI0vSdEJcaI0UGItNcBaxJVlfMHb17we9+4x0FpmFbCsk3s+sRhnSEqXL6zoQtCVKEJgS4OOYMzSuEsuy49RmGUOQ7qgj4EYJDYIaXBEUVQVUoDzsJOQZ/EAgSwKJUzHE9qkkYMYBgUYZE7QgxkVpiOSybjwLaJhBiY5pHTcc7p0SHffG/O9f4EHQanELfdfnHQkKHJ0amn9T0PFgOQSYU2RJoYgGHTdw4q+r6jC4ldP2wuqVKVOZkZ7M8ah0/xByJ9ZpSU0l7iPaEQeur0GKPDYgtnKZxFCPgYMEZxajlfLjF5Pig7CyEGiszhiDx7WPDs8V0erTxvvHPFfN0Sou7JdCh7NU1PVKXrBkZPDD7e7WV16Qy3D0oOqwwRuOwbJHOMy0HltX2PD4lFHzgZV8heBRoR4t4KLImbRzUmKzn7zqMnDYNgrXBQVOyalnFVIE6pckuZ19yfD0nHrvf4XWDTRJQhKzsa19QF9CFS54ZPHBfU7oj/9d1HXO4iIQmRNEQNIOw8EWj9YOrKkDnOSsOtWUWdG0AxxnIwHdNFZdX3qA4b3OwanB2kb+8jB5OS3gdir1SFo65yrIFVx8cLg5fLNflRTWEHUgNL6xNZZrlcbZlvO64bJXdC8EqSQWbGlMiMZbVtaLueg7oClElp2Hpl0w3mH5MOxKT6WKsPPguFhVEOIglrLHafA6QUaHwagI9xHxKFtk88WKypi4zDcYH3gy7IncEaaPrIo3mzz3N+0AXMRwGw3vXcP7+iDzpkVAhXm5Y/+u4ZV7uET8LVNrJsEjcmjtwMDHu+WnO+2mHE0IfE+XJL13kO62rgF6NMxiVV4VCUoMNGitxSVwUig04Y5Tl1kZM5h0EwGK63nsWmwcfEyFk+efOAKI7rTolq2PaRt8+v2TTdcBgKrU9878Gc5aYZrFueyAKEVRvJrSEpPFysObs2GGtoup4Hy0SROz5995CzxZppCS/ePKb1icVmS9cNKW1Mik9DofP0YETTR95btIgMlrP7gFnmRhBVxpnhaJwxKwucGFSVzFqMNXQ+IAiHleX2rOJwZFg38NzRmMtNgw+R07FFxOC3LeIMkzInJGHT7yvK+oQusGwj6z5ii55JZlhtNyzaSOuVcVUQU+TFk4JnjwpWu5bcJI4OMq7bnPl2R1QQEUQHBaaiZFY5Gjvem7dkef6h9/mYCCFwOnFkojgrGCODr+9dZt0nYkpMK4sRxVrDCyf1YJ3bljxzXO0ChU3UuaEMsLhueLDuifujlyflAEVA4XzjeeAjThMY2HTwiy9MQARnBCFxY1qRWYdqRBAut5G3zhbcPBxTZXuCCoFJleNTT0wJ33aPl6MInU8IUDrHwaigrjJ8jKQIm67nfLHjug1MCsdhPeJgVJI5QXWoHj13NAKBb9xfY81wgPN2jXUG1R9d9/1IIfS+sut8BAzrznMyMtyZGZyBMjOMiowuJHrvEZfIneXTtyeUmaFrG0IIHB3NWDeetu/JneFkOmayDOy6gA/ft0drDGVmmNUFmZMBjNxhrWF1tSVq4vnjMSfTitKavXVAiJEYI6UbiiYnIyEk4boPlBloSKS/oCr8kSR453iKALumY9d0oMMHxxnUZU6RGaImjBG63tP6IR/PDTx/NOL+omfZK3Gv9YfTFmCQqlVZfKj+UOYOY4aKUZE58tySuUEv7PrAxSZwa1aRmyEZ8iHgw1BQVQRrh1ZblQ2/pwhdH2g7jwC5M09uAQK8/NQE++yM//7NeyzbAcPDScm0GFpfxhgW6wZnLYqQotL2w6JUDF1S7l1uManneDbG7c3dkKhdYOPNh+zNyNAyn5Q5+b5bvG4D9y+WnK38PuMc5go63+OsRXQgSfbfR2E6KrDOsmg3IHBjUmIZgHq0SU+uA0AZZXA6tozKnKttjzPCwagkxIHUVAW/V20pRtTKPpPz/MLzR5yvOtq+Z7trmNUlVV7QdD2lSxiE+QcgH1mlMIrIkA0qhl3r6XxiVue8OK6GMl0YNhFSGmTv3jKrPMe5wYK6EMit5ZnjioPKcXm9oo/247lA13uCj+RuyLfrquBym1jsIhHB+8QHU4ugStN5YhwWOCsMN8Y5Z6tEGw2ocFAXPH1jxnOnJ+TWfMjinFFm45LO9/gYEFX6CJdN4mhUMM6HCu/7AghVRKEqM44OaqpyKLkv28i86SmKoZAjqkxGFbPJ+OMBkPZZ1a3DKU+djDkYZay7nj9+d07Th8Gj5ftbMGI+FGKcMxhRVr3ycNFwvloTkuJEee6k5pUXbz0GUEncPJoxG5WICj4q75yveOd8RdsrYsA5x7ZpH1ucMYYyzxgVg9T1IbFYd/zpgxVNF6msUDgDDAVW4WNK4a4PeGcR4GiU8fRxzXfOM77+zhwlgRgOxiN27dDiGsTKIFm7rsVJRp077szMUKXtEtu2R8iJqhgxf+4UZOgtpEQblettizHCcW0Y5RnW2n26ax5/3jy2QKFp45BsiXAyzrk5yRAU2afEHysKKJAYhMlQnRp+YJxZpnXJe4ueNoIzUBU57EWPEcPBKOf2yQF1ZbGiTLLhBRfbRB+HfsPXvvse37o/Hzr9+w2crzY8WKwICiHB9Z54K6d7jkkfWqqRoThj9tK5j8r5pqeqcopMhvTYWoo828uN9EOt4CMt4Op6h52W1OUwr9N0HmOU3CpvnW94cLXm55855GBSDD6cGQo3tM+cgUmZY41j00e2V1vmbeJ/vHXFMycTfLK02+ZDiLfeYxEe7QLLrWfTJ45HlrrMEZSYIqKKsUM32pghUviQmG86vvlgTZ9gVuV701eMgKjS9pHrbffxXMCnxPV2x+H0aDA3FWpr+Du3xvyftGW96zi73rDtem4d1sj+VIwIqNCGgBU4mZR0arm3vCYmw7tXDct1YJS771OoKOsWGp8YV4G4t8LpqGBc2sfnPhqVJIWm7YYyWZ84X65pfCSpMi0znjsqsAIi+jjrXDc9MQ7lkPSkydB8F7GTQcBkzlJkltQkTIIyN/Sp4LrtGVWCtZa2S/gYsbakMnC96dj1SuOHkAbKbteRFxlJYdPFD8QAYef35ayQ6ELYG4Zh3SlJ41Amzyw+JDI3gNd7T1Rh0yl57igywTJMmU3qgq73ND7hE6w7/Xi5wLob2tQHoy13DmtGmUHMcBYHlWPbtlxsIn3cUReOusjwXeKbVxfM6gpjHU2I/MF3LxmVJUVm6b2nafsPLMM8lt2Pewr7RkZmHQ+XLV3f83N3D4aixmpHDInjyQgfEtsucb4KbHxkWlnqfTfYOYMV6KNytW54tPYE3Yt7lSdTgkWRQfSsm5amLjidVbiQEFVu1RnTIuePuwUxKZfXG64EJnVBn5R3L1e8M+85no6xZpDMdeX49J0Zi23Le5cbVARUP6AlFIPsq05u39hMWCPcX7asdi23JhlVkbPcdax2DSmBaqLIDM8eVZR2D6QMFeBd79l1ASNK4dw+r3lCC8ic4PKKy23L3ZMh+TgcF1R5xsX1ZqjwlhkxJdZ9ZJTDarPjYquIMUSExbZjMi4Z5w6I1Bmc3CgxsWPR6F5iDyA8czzh7tGIs4s5bUwUec64NKxbz7r1KMK6h1W3Y1YKSS2tV4yzFAbcPtF31g6JEkJQx7KJlEUxDF7wMQAIUTEGVrvEH701xyfDCycVVWZwziIxkEkiqvJgFSmscjSyeIXNLjKbVEM/wSizwnBrWg4zBpoobOJoZFm2cW9xwtOHIwonOGu4WRccTUacrTra3mMLS9CM1a6jyg3bXlh3gZCgLjJmRYYVQUSwIvRBONu0fO/hkrZP1CMhaHrCvoDIW6oamzbYrg8ogk/wrYfXPLhY8MLNMYU1GJRZlsjEsHNCHxMPN5G6zChzgxPlqLLcPSjxIQ2iZE/nJ7MJl6v2Q5SUGFrpB/UwAyT7pstzhyUX236oUOWGJJZ5078/L8izJ2MOKof3Paow33bMt55tYjhvUXb7QUyQqMhbP3JEZr5tf++oHn0Z4RbKp/ahFCNClsGuaTmajLDGsmm7IXGxA7PHFCmzwYd/9vaY3AhlJrj3W2rGIjr0ALMs4715OyRPqtyZDuErprQfoh3kGKqIGI7GOZsuoTqUNo11aErcPaxxMvQnY0pc77Z4HUbw+5D2DiaqyO+C+eV7i81/+AuV4JsXizfePL/+giI/n0S+FBNsGs/FKrDpIWHJnXD7cMJhXZPSsEhFeP50wnHtKMxQCRYYZLKAdZaiyPdE9eGYlBB8TKjK496+2ScbdSbkomSSGLmhYOrMAMRi23D/cklKiaTCrheutz2N93t+lS8p9ufvLbZfuLdYv/GxxuTmu/Zsvu3+8+F49GWB05j0U5sOudp05JmjcEIE3nq0IurA5J+8Oebpw5oQvk84k1HBjdkIHwLoMDgdUuLdvQWgwvHIIaIIgrWGIneDdcT0GCnf9yCGLsq+GKIUdgDTR8u7i4Z1F4lIUjH/RZFfvrfY/taq7c7+SrPC8217drXr/tPhuPwyIne6ED7xcNGYy40Hscx3/fs+xrQSxmVGSgljzDAsZQ2jwtH6QB8Sq6ZDxe5dAJLC6SQnhESVZzjH0GoXoa4Giwkx0fmIs47LdbMHHIxYlm3iatfjk0awX1HMP7632PzWqu2faH74iYel59vubL5t/+NRXX5ZkNtd0E8utq180JZziezaHdO6xMjA6mWeUWSWTTsIkgeXSzZNz7xJwymjZBLxvWc6KkGgLjOKfU2w90OrvHCWBFxs/WPF0kclaUqK/XIS+Sf3F5svPunGf2Q94Ec9b56v3vj2xeqXkphfUMyXPghAFyCl4SebLnCxGhoZgnCxbHk439EFQ0jm+ySgQkrQJ+FivWO57TDG0QXlbL6l7RNuH9t3H2jx70XdlxT3C/cWm196MN/8pe4Q/JUvTHzyxsFnVfRXRfmCoJJb5dN3Z9TOcrla0atw98YR213Datfy9uUwarvzaV/aUko7DEjfnhWMioyiLLlcLslQbhzMaEPirYsNjQ8IRhV+J4n85v355q98p+jHdmPkE6eHrxhNrwv6ORW1pYWDahiI3sRhJrhyhk03TJMMtb2IT4rs09cqs/QhURYZhYMYItte6aICJip8FXjt3mLzY7sx8mO/M/SJ04NXBF4XTZ+XYSKWoszpe0+MMB0XhD6y6fxjAMrcktmMbdMgdiiJBz+oPZCkyFdAXvthoexv5K2xT5zOXjXKr4H+o+8LTTiYlKSYWG77xwDUZY4VYdO0f66RYb6UkN+4v9j89Nwa+wiL+KxR/VXgC6AyND+Epgt7DoA8s4QYhgwPUYHfUeQ37y02P733Bj+CLF9V0dcE/fwwUfF9DtgnRVGRryQxr9+fr/723Bz9SI5QfV3Qz4UUrU8aFfmqqLz23vXmr/3u8E/sefHG9NXnT8b/+qnD8av8/+cn9/w/Sx/UOQbd9DAAAAAASUVORK5CYII="#;
-
-    format!("{{\"description\": {{\"text\": \"{}\"}}, \"version\": {{\"name\": \"{}\", \"protocol\": {}}}, \"players\": {{\"max\": {}, \"online\": {}}}, \"favicon\": \"{}\"}}", text, version.name, version.protocol, players.max, players.online, image)
-}
 
-fn get_stored_registry_bytes() -> &'static [u8] {
-    let bytes = include_bytes!("../../src/files/registryCodec.bin");
+    // Only login and play have a way to tell the client why it's being dropped
+    pub fn disconnect(state: State, reason: TextComponent) -> Option<Self> {
+        match state {
+            State::Login => Some(Self::LoginDisconnect { reason }),
+            State::Play => Some(Self::Disconnect { reason }),
+            State::Shake | State::Status => None,
+        }
+    }
 
-    bytes
+    pub async fn send_to(value: Arc<Self>, chan: &Sender<Arc<S2c>>) -> io::Result<()> {
+        chan.send(value).await.map_err(io::Error::other)
+    }
 }
 
-fn get_stored_packet_bytes() -> &'static [u8] {
-    let bytes = include_bytes!("../../src/files/fullLoginPacket.bin");
+async fn write_properties(
+    properties: &[ProfileProperty],
+    writer: &mut impl AsyncWriteOwnExt,
+) -> io::Result<()> {
+    writer.write_var_int(properties.len() as VarInt).await?;
+    for property in properties {
+        writer.write_string(&property.name).await?;
+        writer.write_string(&property.value).await?;
+        writer.write_bool(property.signature.is_some()).await?;
+        if let Some(signature) = &property.signature {
+            writer.write_string(signature).await?;
+        }
+    }
 
-    bytes
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
@@ -330,3 +752,485 @@ impl PalettedContainer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use crate::tcp::protocol::get_protocol;
+
+    // Packet id plus body, the way a client sends them once the frame is stripped
+    async fn read_play(packet_id: VarInt, body: &[u8]) -> C2s {
+        let mut bytes = vec![];
+        bytes.write_var_int(packet_id).await.unwrap();
+        bytes.extend_from_slice(body);
+
+        let mut reader = &bytes[..];
+        let packet = C2s::read(get_protocol(762).unwrap(), State::Play, &mut reader)
+            .await
+            .unwrap();
+        assert!(reader.is_empty(), "{:?} left bytes unread", packet);
+
+        packet
+    }
+
+    fn position(x: i64, y: i64, z: i64) -> [u8; 8] {
+        (((x & 0x3FFFFFF) << 38) | ((z & 0x3FFFFFF) << 12) | (y & 0xFFF)).to_be_bytes()
+    }
+
+    #[tokio::test]
+    async fn reads_confirm_teleportation() {
+        let packet = read_play(0x00, &[0x2a]).await;
+        assert!(matches!(
+            packet,
+            C2s::ConfirmTeleportation { teleport_id: 42 }
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_message_acknowledgment() {
+        let packet = read_play(0x03, &[0x05]).await;
+        assert!(matches!(
+            packet,
+            C2s::MessageAcknowledgment { message_count: 5 }
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_chat_command() {
+        let mut body = vec![];
+        body.write_string("tp 0 64 0").await.unwrap();
+        body.extend_from_slice(&7u64.to_be_bytes());
+        body.extend_from_slice(&9u64.to_be_bytes());
+        // One signed argument, then the last seen messages
+        body.write_var_int(1).await.unwrap();
+        body.write_string("x").await.unwrap();
+        body.extend_from_slice(&[0; 256]);
+        body.write_var_int(0).await.unwrap();
+        body.extend_from_slice(&[0; 3]);
+
+        let packet = read_play(0x04, &body).await;
+        assert!(matches!(
+            packet,
+            C2s::ChatCommand { ref command, timestamp: 7, salt: 9 } if command == "tp 0 64 0"
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_chat_message() {
+        let mut body = vec![];
+        body.write_string("hello").await.unwrap();
+        body.extend_from_slice(&7u64.to_be_bytes());
+        body.extend_from_slice(&9u64.to_be_bytes());
+        body.write_bool(true).await.unwrap();
+        body.extend_from_slice(&[0xab; 256]);
+        body.write_var_int(0).await.unwrap();
+        body.extend_from_slice(&[0; 3]);
+
+        let packet = read_play(0x05, &body).await;
+        match packet {
+            C2s::ChatMessage {
+                message,
+                timestamp,
+                salt,
+                signature,
+            } => {
+                assert_eq!(message, "hello");
+                assert_eq!((timestamp, salt), (7, 9));
+                assert_eq!(signature, Some(vec![0xab; 256]));
+            }
+            packet => panic!("Unexpected {:?}", packet),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_unsigned_chat_message() {
+        let mut body = vec![];
+        body.write_string("hello").await.unwrap();
+        body.extend_from_slice(&[0; 16]);
+        body.write_bool(false).await.unwrap();
+        body.write_var_int(0).await.unwrap();
+        body.extend_from_slice(&[0; 3]);
+
+        let packet = read_play(0x05, &body).await;
+        assert!(matches!(
+            packet,
+            C2s::ChatMessage {
+                signature: None,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_client_command() {
+        let packet = read_play(0x07, &[0x01]).await;
+        assert!(matches!(packet, C2s::ClientCommand { action_id: 1 }));
+    }
+
+    #[tokio::test]
+    async fn reads_client_information() {
+        let mut body = vec![];
+        body.write_string("en_us").await.unwrap();
+        body.extend_from_slice(&[12, 0x00, 0x01, 0x7f, 0x01, 0x00, 0x01]);
+
+        let packet = read_play(0x08, &body).await;
+        match packet {
+            C2s::ClientInformation {
+                locale,
+                view_distance,
+                chat_mode,
+                chat_colors,
+                displayed_skin_parts,
+                main_hand,
+                enable_text_filtering,
+                allow_server_listings,
+            } => {
+                assert_eq!(locale, "en_us");
+                assert_eq!(view_distance, 12);
+                assert_eq!(chat_mode, 0);
+                assert!(chat_colors);
+                assert_eq!(displayed_skin_parts, 0x7f);
+                assert_eq!(main_hand, 1);
+                assert!(!enable_text_filtering);
+                assert!(allow_server_listings);
+            }
+            packet => panic!("Unexpected {:?}", packet),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_close_container() {
+        let packet = read_play(0x0C, &[0x03]).await;
+        assert!(matches!(packet, C2s::CloseContainer { window_id: 3 }));
+    }
+
+    #[tokio::test]
+    async fn reads_plugin_message() {
+        let mut body = vec![];
+        body.write_string("minecraft:brand").await.unwrap();
+        body.write_string("vanilla").await.unwrap();
+
+        let packet = read_play(0x0D, &body).await;
+        match packet {
+            C2s::PluginMessage { channel, data } => {
+                assert_eq!(channel, "minecraft:brand");
+                assert_eq!(data, b"\x07vanilla");
+            }
+            packet => panic!("Unexpected {:?}", packet),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_keep_alive() {
+        let packet = read_play(0x12, &0x0102030405060708u64.to_be_bytes()).await;
+        assert!(matches!(
+            packet,
+            C2s::KeepAlive {
+                id: 0x0102030405060708
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_set_player_position() {
+        let mut body = vec![];
+        for value in [1.5f64, 64.0, -3.25] {
+            body.extend_from_slice(&value.to_be_bytes());
+        }
+        body.push(0x01);
+
+        let packet = read_play(0x14, &body).await;
+        assert!(matches!(
+            packet,
+            C2s::SetPlayerPosition { x, y, z, on_ground: true } if (x, y, z) == (1.5, 64.0, -3.25)
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_set_player_position_and_rotation() {
+        let mut body = vec![];
+        for value in [1.5f64, 64.0, -3.25] {
+            body.extend_from_slice(&value.to_be_bytes());
+        }
+        body.extend_from_slice(&90f32.to_be_bytes());
+        body.extend_from_slice(&(-45f32).to_be_bytes());
+        body.push(0x00);
+
+        let packet = read_play(0x15, &body).await;
+        assert!(matches!(
+            packet,
+            C2s::SetPlayerPositionAndRotation { x, y, z, yaw, pitch, on_ground: false }
+                if (x, y, z) == (1.5, 64.0, -3.25) && (yaw, pitch) == (90.0, -45.0)
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_set_player_rotation() {
+        let mut body = vec![];
+        body.extend_from_slice(&180f32.to_be_bytes());
+        body.extend_from_slice(&12.5f32.to_be_bytes());
+        body.push(0x01);
+
+        let packet = read_play(0x16, &body).await;
+        assert!(matches!(
+            packet,
+            C2s::SetPlayerRotation { yaw, pitch, on_ground: true } if (yaw, pitch) == (180.0, 12.5)
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_set_player_on_ground() {
+        let packet = read_play(0x17, &[0x01]).await;
+        assert!(matches!(packet, C2s::SetPlayerOnGround { on_ground: true }));
+    }
+
+    #[tokio::test]
+    async fn reads_player_abilities() {
+        let packet = read_play(0x1C, &[0x02]).await;
+        assert!(matches!(packet, C2s::PlayerAbilities { flags: 0x02 }));
+    }
+
+    #[tokio::test]
+    async fn reads_player_action() {
+        let mut body = vec![0x00];
+        body.extend_from_slice(&position(-10, -64, 300));
+        body.extend_from_slice(&[0x01, 0x07]);
+
+        let packet = read_play(0x1D, &body).await;
+        match packet {
+            C2s::PlayerAction {
+                status,
+                location,
+                face,
+                sequence,
+            } => {
+                assert_eq!(status, 0);
+                assert_eq!((location.x, location.y, location.z), (-10, -64, 300));
+                assert_eq!(face, 1);
+                assert_eq!(sequence, 7);
+            }
+            packet => panic!("Unexpected {:?}", packet),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_player_command() {
+        let packet = read_play(0x1E, &[0x05, 0x03, 0x00]).await;
+        assert!(matches!(
+            packet,
+            C2s::PlayerCommand {
+                entity_id: 5,
+                action_id: 3,
+                jump_boost: 0
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_set_held_item() {
+        let packet = read_play(0x28, &4i16.to_be_bytes()).await;
+        assert!(matches!(packet, C2s::SetHeldItem { slot: 4 }));
+    }
+
+    #[tokio::test]
+    async fn reads_swing_arm() {
+        let packet = read_play(0x2F, &[0x01]).await;
+        assert!(matches!(packet, C2s::SwingArm { hand: 1 }));
+    }
+
+    #[tokio::test]
+    async fn reads_use_item_on() {
+        let mut body = vec![0x00];
+        body.extend_from_slice(&position(1, 2, 3));
+        body.push(0x04);
+        for value in [0.5f32, 1.0, 0.25] {
+            body.extend_from_slice(&value.to_be_bytes());
+        }
+        body.extend_from_slice(&[0x00, 0x09]);
+
+        let packet = read_play(0x31, &body).await;
+        match packet {
+            C2s::UseItemOn {
+                hand,
+                location,
+                face,
+                cursor_x,
+                cursor_y,
+                cursor_z,
+                inside_block,
+                sequence,
+            } => {
+                assert_eq!(hand, 0);
+                assert_eq!((location.x, location.y, location.z), (1, 2, 3));
+                assert_eq!(face, 4);
+                assert_eq!((cursor_x, cursor_y, cursor_z), (0.5, 1.0, 0.25));
+                assert!(!inside_block);
+                assert_eq!(sequence, 9);
+            }
+            packet => panic!("Unexpected {:?}", packet),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_use_item() {
+        let packet = read_play(0x32, &[0x01, 0x02]).await;
+        assert!(matches!(
+            packet,
+            C2s::UseItem {
+                hand: 1,
+                sequence: 2
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn skips_unhandled_play_packets() {
+        let packet = read_play(0x7f, &[]).await;
+        assert!(matches!(packet, C2s::Mock));
+    }
+
+    async fn write_packet(packet: S2c) -> Vec<u8> {
+        let mut bytes = vec![];
+        packet
+            .write_to(get_protocol(762).unwrap(), &mut bytes)
+            .await
+            .unwrap();
+
+        bytes
+    }
+
+    // Login (play) as a 1.19.4 server sends it, with the codec we generate
+    #[tokio::test]
+    async fn login_play_matches_vanilla() {
+        let packet = S2c::LoginPlay {
+            entity_id: 1,
+            is_hardcore: false,
+            gamemode: 1,
+            previous_gamemode: None,
+            dimension_names: vec![
+                "minecraft:overworld".to_owned(),
+                "minecraft:the_end".to_owned(),
+                "minecraft:the_nether".to_owned(),
+            ],
+            registry_codec: registry::get_packed_registry_codec(get_protocol(762).unwrap()),
+            dimension_type: "minecraft:overworld".to_owned(),
+            dimension_name: "minecraft:overworld".to_owned(),
+            hashed_seed: 0x6246d8aa74e1fb87,
+            max_players: 20,
+            view_distance: 10,
+            simulation_distance: 10,
+            reduced_debug_info: false,
+            enable_respawn_screen: true,
+            is_debug: false,
+            is_flat: true,
+            death_location: None,
+            portal_cooldown: 0,
+        };
+
+        assert!(
+            write_packet(packet).await == include_bytes!("../files/fullLoginPacket.bin"),
+            "Login (play) differs from the vanilla packet"
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_player_info_add() {
+        let packet = S2c::PlayerInfoAdd {
+            players: vec![PlayerInfo {
+                uuid: vec![0xaa; 16],
+                name: "Notch".to_owned(),
+                properties: vec![ProfileProperty {
+                    name: "textures".to_owned(),
+                    value: "e30=".to_owned(),
+                    signature: None,
+                }],
+                gamemode: 1,
+                latency: Some(Duration::from_millis(300)),
+            }],
+        };
+
+        let mut expected = vec![0x3a, 0x1d, 0x01];
+        expected.extend_from_slice(&[0xaa; 16]);
+        expected.extend_from_slice(b"\x05Notch\x01\x08textures\x04e30=\x00");
+        // Creative, listed, then 300 as a VarInt
+        expected.extend_from_slice(&[0x01, 0x01, 0xac, 0x02]);
+        assert_eq!(write_packet(packet).await, expected);
+    }
+
+    #[tokio::test]
+    async fn writes_player_info_latency() {
+        let packet = S2c::PlayerInfoLatency {
+            players: vec![(vec![0xbb; 16], Duration::from_millis(42))],
+        };
+
+        let mut expected = vec![0x3a, 0x10, 0x01];
+        expected.extend_from_slice(&[0xbb; 16]);
+        expected.push(42);
+        assert_eq!(write_packet(packet).await, expected);
+    }
+
+    #[tokio::test]
+    async fn writes_player_info_remove() {
+        let packet = S2c::PlayerInfoRemove {
+            uuids: vec![vec![0xcc; 16]],
+        };
+
+        let mut expected = vec![0x39, 0x01];
+        expected.extend_from_slice(&[0xcc; 16]);
+        assert_eq!(write_packet(packet).await, expected);
+    }
+
+    #[test]
+    fn status_sample_has_latency() {
+        let sample = PlayerSample {
+            name: "Notch".to_owned(),
+            id: "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_owned(),
+            latency: Some(42),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&sample).unwrap(),
+            r#"{"name":"Notch","id":"069a79f4-44e9-4726-a5be-fca90e38aaf5","latency":42}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_login_packets() {
+        let result = C2s::read(get_protocol(762).unwrap(), State::Login, &mut &[0x7f][..]).await;
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn disconnect_matches_state() {
+        let reason = || TextComponent::text("Server closed");
+
+        assert!(matches!(
+            S2c::disconnect(State::Login, reason()),
+            Some(S2c::LoginDisconnect { .. })
+        ));
+        assert!(matches!(
+            S2c::disconnect(State::Play, reason()),
+            Some(S2c::Disconnect { .. })
+        ));
+        assert!(S2c::disconnect(State::Shake, reason()).is_none());
+        assert!(S2c::disconnect(State::Status, reason()).is_none());
+    }
+
+    #[tokio::test]
+    async fn writes_disconnect_reasons() {
+        let reason = TextComponent::text("Bye");
+        let json = br#"{"text":"Bye"}"#;
+        assert_eq!(reason.to_json().as_bytes(), json);
+
+        let mut expected = vec![0x00, json.len() as u8];
+        expected.extend_from_slice(json);
+        let packet = S2c::LoginDisconnect {
+            reason: reason.clone(),
+        };
+        assert_eq!(write_packet(packet).await, expected);
+
+        expected[0] = 0x1A;
+        assert_eq!(write_packet(S2c::Disconnect { reason }).await, expected);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::config::get_config;
+use crate::nbt::NbtFormat;
+use crate::tcp::state::State;
+use crate::VarInt;
+use std::io;
+
+// Packets as the server sees them, their ids depend on the protocol version
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Serverbound {
+    Handshake,
+    StatusRequest,
+    PingRequest,
+    LoginStart,
+    EncryptionResponse,
+    ConfirmTeleportation,
+    MessageAcknowledgment,
+    ChatCommand,
+    ChatMessage,
+    ClientCommand,
+    ClientInformation,
+    CloseContainer,
+    PluginMessage,
+    KeepAlive,
+    SetPlayerPosition,
+    SetPlayerPositionAndRotation,
+    SetPlayerRotation,
+    SetPlayerOnGround,
+    PlayerAbilities,
+    PlayerAction,
+    PlayerCommand,
+    SetHeldItem,
+    SwingArm,
+    UseItemOn,
+    UseItem,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Clientbound {
+    StatusResponse,
+    PongResponse,
+    LoginDisconnect,
+    EncryptionRequest,
+    LoginSuccess,
+    SetCompression,
+    Disconnect,
+    KeepAlive,
+    ChunkDataAndLight,
+    LoginPlay,
+    PlayerInfoRemove,
+    PlayerInfoUpdate,
+    SetDefaultSpawnPosition,
+}
+
+#[derive(Debug)]
+pub struct Protocol {
+    pub version: u32,
+    pub name: &'static str,
+    serverbound: &'static [(State, VarInt, Serverbound)],
+    clientbound: &'static [(Clientbound, VarInt)],
+    // Login (play) ends with the portal cooldown since 1.20
+    pub login_portal_cooldown: bool,
+    // Light data lost the trust edges flag in 1.20
+    pub light_trust_edges: bool,
+    // Root compounds in packets are nameless from 1.20.2 on
+    pub nbt_format: NbtFormat,
+    // The registry codec needs the damage types 1.20 added
+    pub damage_types_1_20: bool,
+}
+
+impl Protocol {
+    pub fn serverbound(&self, state: State, packet_id: VarInt) -> Option<Serverbound> {
+        self.serverbound
+            .iter()
+            .find(|(s, id, _)| *s == state && *id == packet_id)
+            .map(|(_, _, packet)| *packet)
+    }
+
+    pub fn clientbound_id(&self, packet: Clientbound) -> io::Result<VarInt> {
+        self.clientbound
+            .iter()
+            .find(|(p, _)| *p == packet)
+            .map(|(_, id)| *id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("{:?} has no id in protocol {}", packet, self.version),
+                )
+            })
+    }
+}
+
+const SERVERBOUND_1_19_4: &[(State, VarInt, Serverbound)] = &[
+    (State::Shake, 0x00, Serverbound::Handshake),
+    (State::Status, 0x00, Serverbound::StatusRequest),
+    (State::Status, 0x01, Serverbound::PingRequest),
+    (State::Login, 0x00, Serverbound::LoginStart),
+    (State::Login, 0x01, Serverbound::EncryptionResponse),
+    (State::Play, 0x00, Serverbound::ConfirmTeleportation),
+    (State::Play, 0x03, Serverbound::MessageAcknowledgment),
+    (State::Play, 0x04, Serverbound::ChatCommand),
+    (State::Play, 0x05, Serverbound::ChatMessage),
+    (State::Play, 0x07, Serverbound::ClientCommand),
+    (State::Play, 0x08, Serverbound::ClientInformation),
+    (State::Play, 0x0C, Serverbound::CloseContainer),
+    (State::Play, 0x0D, Serverbound::PluginMessage),
+    (State::Play, 0x12, Serverbound::KeepAlive),
+    (State::Play, 0x14, Serverbound::SetPlayerPosition),
+    (State::Play, 0x15, Serverbound::SetPlayerPositionAndRotation),
+    (State::Play, 0x16, Serverbound::SetPlayerRotation),
+    (State::Play, 0x17, Serverbound::SetPlayerOnGround),
+    (State::Play, 0x1C, Serverbound::PlayerAbilities),
+    (State::Play, 0x1D, Serverbound::PlayerAction),
+    (State::Play, 0x1E, Serverbound::PlayerCommand),
+    (State::Play, 0x28, Serverbound::SetHeldItem),
+    (State::Play, 0x2F, Serverbound::SwingArm),
+    (State::Play, 0x31, Serverbound::UseItemOn),
+    (State::Play, 0x32, Serverbound::UseItem),
+];
+
+const CLIENTBOUND_1_19_4: &[(Clientbound, VarInt)] = &[
+    (Clientbound::StatusResponse, 0x00),
+    (Clientbound::PongResponse, 0x01),
+    (Clientbound::LoginDisconnect, 0x00),
+    (Clientbound::EncryptionRequest, 0x01),
+    (Clientbound::LoginSuccess, 0x02),
+    (Clientbound::SetCompression, 0x03),
+    (Clientbound::Disconnect, 0x1A),
+    (Clientbound::KeepAlive, 0x23),
+    (Clientbound::ChunkDataAndLight, 0x24),
+    (Clientbound::LoginPlay, 0x28),
+    (Clientbound::PlayerInfoRemove, 0x39),
+    (Clientbound::PlayerInfoUpdate, 0x3A),
+    (Clientbound::SetDefaultSpawnPosition, 0x50),
+];
+
+// Checked against the 1.20.1 packet list, none of the packets we use moved
+const SERVERBOUND_1_20_1: &[(State, VarInt, Serverbound)] = &[
+    (State::Shake, 0x00, Serverbound::Handshake),
+    (State::Status, 0x00, Serverbound::StatusRequest),
+    (State::Status, 0x01, Serverbound::PingRequest),
+    (State::Login, 0x00, Serverbound::LoginStart),
+    (State::Login, 0x01, Serverbound::EncryptionResponse),
+    (State::Play, 0x00, Serverbound::ConfirmTeleportation),
+    (State::Play, 0x03, Serverbound::MessageAcknowledgment),
+    (State::Play, 0x04, Serverbound::ChatCommand),
+    (State::Play, 0x05, Serverbound::ChatMessage),
+    (State::Play, 0x07, Serverbound::ClientCommand),
+    (State::Play, 0x08, Serverbound::ClientInformation),
+    (State::Play, 0x0C, Serverbound::CloseContainer),
+    (State::Play, 0x0D, Serverbound::PluginMessage),
+    (State::Play, 0x12, Serverbound::KeepAlive),
+    (State::Play, 0x14, Serverbound::SetPlayerPosition),
+    (State::Play, 0x15, Serverbound::SetPlayerPositionAndRotation),
+    (State::Play, 0x16, Serverbound::SetPlayerRotation),
+    (State::Play, 0x17, Serverbound::SetPlayerOnGround),
+    (State::Play, 0x1C, Serverbound::PlayerAbilities),
+    (State::Play, 0x1D, Serverbound::PlayerAction),
+    (State::Play, 0x1E, Serverbound::PlayerCommand),
+    (State::Play, 0x28, Serverbound::SetHeldItem),
+    (State::Play, 0x2F, Serverbound::SwingArm),
+    (State::Play, 0x31, Serverbound::UseItemOn),
+    (State::Play, 0x32, Serverbound::UseItem),
+];
+
+const CLIENTBOUND_1_20_1: &[(Clientbound, VarInt)] = &[
+    (Clientbound::StatusResponse, 0x00),
+    (Clientbound::PongResponse, 0x01),
+    (Clientbound::LoginDisconnect, 0x00),
+    (Clientbound::EncryptionRequest, 0x01),
+    (Clientbound::LoginSuccess, 0x02),
+    (Clientbound::SetCompression, 0x03),
+    (Clientbound::Disconnect, 0x1A),
+    (Clientbound::KeepAlive, 0x23),
+    (Clientbound::ChunkDataAndLight, 0x24),
+    (Clientbound::LoginPlay, 0x28),
+    (Clientbound::PlayerInfoRemove, 0x39),
+    (Clientbound::PlayerInfoUpdate, 0x3A),
+    (Clientbound::SetDefaultSpawnPosition, 0x50),
+];
+
+// Protocol versions this server can talk, oldest first
+pub const PROTOCOLS: &[Protocol] = &[
+    Protocol {
+        version: 762,
+        name: "1.19.4",
+        serverbound: SERVERBOUND_1_19_4,
+        clientbound: CLIENTBOUND_1_19_4,
+        login_portal_cooldown: false,
+        light_trust_edges: true,
+        nbt_format: NbtFormat::Named,
+        damage_types_1_20: false,
+    },
+    Protocol {
+        version: 763,
+        name: "1.20.1",
+        serverbound: SERVERBOUND_1_20_1,
+        clientbound: CLIENTBOUND_1_20_1,
+        login_portal_cooldown: true,
+        light_trust_edges: false,
+        nbt_format: NbtFormat::Named,
+        damage_types_1_20: true,
+    },
+];
+
+pub fn get_protocol(version: u32) -> Option<&'static Protocol> {
+    PROTOCOLS
+        .iter()
+        .find(|protocol| protocol.version == version)
+}
+
+// Used until the handshake tells us what the client speaks
+pub fn get_default_protocol() -> &'static Protocol {
+    get_protocol(get_config().protocol_version).unwrap_or(&PROTOCOLS[PROTOCOLS.len() - 1])
+}
+
+fn supported_names() -> String {
+    PROTOCOLS
+        .iter()
+        .map(|protocol| protocol.name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Same wording vanilla uses when the versions don't match
+pub fn unsupported_message(version: u32) -> String {
+    if version < PROTOCOLS[0].version {
+        format!("Outdated client! Please use {}", supported_names())
+    } else {
+        format!("Outdated server! I'm still on {}", supported_names())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENTBOUND: &[Clientbound] = &[
+        Clientbound::StatusResponse,
+        Clientbound::PongResponse,
+        Clientbound::LoginDisconnect,
+        Clientbound::EncryptionRequest,
+        Clientbound::LoginSuccess,
+        Clientbound::SetCompression,
+        Clientbound::Disconnect,
+        Clientbound::KeepAlive,
+        Clientbound::ChunkDataAndLight,
+        Clientbound::LoginPlay,
+        Clientbound::PlayerInfoRemove,
+        Clientbound::PlayerInfoUpdate,
+        Clientbound::SetDefaultSpawnPosition,
+    ];
+
+    #[test]
+    fn every_protocol_maps_every_clientbound_packet() {
+        for protocol in PROTOCOLS {
+            for packet in CLIENTBOUND {
+                assert!(
+                    protocol.clientbound_id(*packet).is_ok(),
+                    "{:?} is missing from {}",
+                    packet,
+                    protocol.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn missing_clientbound_packet_is_an_error() {
+        let protocol = Protocol {
+            clientbound: &[],
+            ..PROTOCOLS[0]
+        };
+
+        let error = protocol.clientbound_id(Clientbound::KeepAlive).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn serverbound_ids_depend_on_state() {
+        let protocol = get_protocol(763).unwrap();
+
+        assert_eq!(
+            protocol.serverbound(State::Status, 0x00),
+            Some(Serverbound::StatusRequest)
+        );
+        assert_eq!(
+            protocol.serverbound(State::Login, 0x00),
+            Some(Serverbound::LoginStart)
+        );
+        assert_eq!(
+            protocol.serverbound(State::Play, 0x00),
+            Some(Serverbound::ConfirmTeleportation)
+        );
+        assert_eq!(protocol.serverbound(State::Play, 0x7f), None);
+    }
+
+    #[test]
+    fn names_supported_versions_in_mismatch_message() {
+        assert_eq!(
+            unsupported_message(761),
+            "Outdated client! Please use 1.19.4, 1.20.1"
+        );
+        assert_eq!(
+            unsupported_message(764),
+            "Outdated server! I'm still on 1.19.4, 1.20.1"
+        );
+    }
+}
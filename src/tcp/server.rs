@@ -1,16 +1,41 @@
-use crate::tcp::client;
-use crate::tcp::event::Event;
+use crate::config::get_config;
+use crate::tcp::chat::TextComponent;
+use crate::tcp::event::{self, Event};
 use crate::tcp::packet::S2c;
+use crate::tcp::session::ProfileProperty;
 use crate::tcp::state::State;
+use crate::tcp::{client, crypto, legacy};
 use crate::{log, measure};
 use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime};
 use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::sync::Mutex;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, oneshot, RwLock};
 
-type ClientMap = Mutex<HashMap<u32, Sender<Arc<S2c>>>>;
+// How long a disconnect packet may take to reach the client
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+const SLOW_CLIENT_REASON: &str = "Too many packets queued, the connection is too slow";
+
+// Packets a client's own handlers queue, they wait for room in it
+const CLIENT_QUEUE_LEN: usize = 16;
+
+// Packets events queue for a client, the event loop can't wait so a client is kicked once it's full
+const EVENT_QUEUE_LEN: usize = 256;
+
+struct Client {
+    events: Sender<Arc<S2c>>,
+    connection: Arc<Mutex<client::Connection>>,
+    kick: Option<oneshot::Sender<String>>,
+}
+
+type ClientMap = Mutex<HashMap<u32, Client>>;
 
 fn get_clients() -> &'static ClientMap {
     static CLIENTS: OnceLock<ClientMap> = OnceLock::new();
@@ -18,16 +43,76 @@ fn get_clients() -> &'static ClientMap {
     CLIENTS.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-pub async fn start_server(host: &'_ str, port: &'_ str) {
+#[derive(Debug, Clone)]
+pub struct ClientInfo {
+    pub id: u32,
+    pub address: SocketAddr,
+    pub username: Option<String>,
+    pub uuid: Option<Vec<u8>>,
+    pub properties: Vec<ProfileProperty>,
+    pub state: State,
+    pub position: Option<(f64, f64, f64)>,
+    pub latency: Option<Duration>,
+    pub connected_at: SystemTime,
+}
+
+pub async fn get_client(id: u32) -> Option<ClientInfo> {
+    let connection = get_clients().lock().await.get(&id)?.connection.clone();
+    let info = connection.lock().await.info();
+
+    Some(info)
+}
+
+pub async fn get_client_by_name(username: &str) -> Option<ClientInfo> {
+    get_online_players()
+        .await
+        .into_iter()
+        .find(|info| info.username.as_deref() == Some(username))
+}
+
+pub async fn list_clients() -> Vec<ClientInfo> {
+    let connections = get_clients()
+        .lock()
+        .await
+        .values()
+        .map(|client| client.connection.clone())
+        .collect::<Vec<_>>();
+
+    let mut clients = vec![];
+    for connection in connections {
+        clients.push(connection.lock().await.info());
+    }
+    clients.sort_by_key(|info| info.id);
+
+    clients
+}
+
+// Clients that finished the login and are in game
+pub async fn get_online_players() -> Vec<ClientInfo> {
+    list_clients()
+        .await
+        .into_iter()
+        .filter(|info| info.state == State::Play)
+        .collect()
+}
+
+pub async fn start_server(host: &'_ str, port: u16) {
     log::debug!("Starting server...");
 
+    let config = get_config();
+    if config.enable_encryption || config.online_mode {
+        crypto::get_server_key();
+    }
+
     let listener = TcpListener::bind(format!("{}:{}", host, port))
         .await
         .unwrap();
 
     log::info!("Serving at {}:{}", host, port);
 
-    let (_event_channel_writer, mut event_channel_reader) = mpsc::channel(16);
+    let (event_channel_writer, mut event_channel_reader) = mpsc::channel(16);
+    event::init_event_bus(event_channel_writer);
+
     tokio::select! {
         _ = event_loop(&mut event_channel_reader) => {}
         _ = serve(listener) => {}
@@ -35,19 +120,105 @@ pub async fn start_server(host: &'_ str, port: &'_ str) {
 }
 
 fn generate_client_id() -> u32 {
-    0
+    static NEXT_CLIENT_ID: AtomicU32 = AtomicU32::new(0);
+
+    NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed)
 }
 
 async fn event_loop(event_channel_reader: &mut Receiver<Event>) {
     while let Some(event) = event_channel_reader.recv().await {
         match event {
-            Event::BroadcastEvent { packet } => {
-                let clients = get_clients().lock().await;
+            Event::Broadcast { packet } => {
+                let clients = get_clients()
+                    .lock()
+                    .await
+                    .iter()
+                    .map(|(id, client)| (*id, client.events.clone(), client.connection.clone()))
+                    .collect::<Vec<_>>();
 
-                for (_, client) in clients.iter() {
-                    client.send(packet.clone()).await.expect("closed channel");
+                // Play packets mean nothing to clients that are still pinging or logging in
+                for (client_id, sender, connection) in clients {
+                    if connection.lock().await.state == State::Play {
+                        deliver(client_id, &sender, packet.clone()).await;
+                    }
                 }
             }
+            Event::SendTo { client_id, packet } => {
+                let sender = get_clients()
+                    .lock()
+                    .await
+                    .get(&client_id)
+                    .map(|client| client.events.clone());
+
+                match sender {
+                    Some(sender) => deliver(client_id, &sender, packet).await,
+                    None => log::debug!("Tried to send a packet to unknown client {}", client_id),
+                }
+            }
+            Event::SendNear {
+                position,
+                radius,
+                packet,
+            } => {
+                let clients = get_clients()
+                    .lock()
+                    .await
+                    .iter()
+                    .map(|(id, client)| (*id, client.events.clone(), client.connection.clone()))
+                    .collect::<Vec<_>>();
+
+                for (client_id, sender, connection) in clients {
+                    let player_position = {
+                        let connection = connection.lock().await;
+                        (connection.state == State::Play)
+                            .then_some(connection.position)
+                            .flatten()
+                    };
+
+                    if let Some((x, y, z)) = player_position {
+                        let distance = ((x - position.x as f64).powi(2)
+                            + (y - position.y as f64).powi(2)
+                            + (z - position.z as f64).powi(2))
+                        .sqrt();
+
+                        if distance <= radius {
+                            deliver(client_id, &sender, packet.clone()).await;
+                        }
+                    }
+                }
+            }
+            Event::Kick { client_id, reason } => kick(client_id, reason).await,
+        }
+    }
+}
+
+async fn kick(client_id: u32, reason: String) {
+    let kick = get_clients()
+        .lock()
+        .await
+        .get_mut(&client_id)
+        .and_then(|client| client.kick.take());
+
+    match kick {
+        Some(kick) => {
+            let _ = kick.send(reason);
+        }
+        None => log::debug!("Tried to kick unknown client {}", client_id),
+    }
+}
+
+// Waiting on one slow client would hold up every other event, so it gets kicked instead
+// once its event queue is full. Packets its own handlers queued, like chunks, don't count.
+// The client may also disconnect while an event is being delivered, that's fine.
+async fn deliver(client_id: u32, sender: &Sender<Arc<S2c>>, packet: Arc<S2c>) {
+    match sender.try_send(packet) {
+        Ok(()) => {}
+        Err(TrySendError::Full(_)) => {
+            log::info!("Client {} can't keep up with its packets", client_id);
+            kick(client_id, SLOW_CLIENT_REASON.to_owned()).await;
+        }
+        Err(TrySendError::Closed(_)) => {
+            log::debug!("Dropped event packet for a closed connection")
         }
     }
 }
@@ -63,28 +234,419 @@ async fn serve(listener: TcpListener) {
         };
 
         log::debug!("New client connected: {}", addr);
-        tokio::task::spawn(handle_connection(socket));
+        tokio::task::spawn(handle_connection(socket, addr));
     }
 }
 
-async fn handle_connection(socket: TcpStream) {
+async fn handle_connection(socket: TcpStream, addr: SocketAddr) {
+    let mut first_byte = [0; 1];
+    if let Ok(1) = socket.peek(&mut first_byte).await {
+        if first_byte[0] == legacy::LEGACY_PING {
+            if let Err(e) = legacy::handle_legacy_ping(socket).await {
+                log::debug!("Failed to answer legacy ping from {}: {}", addr, e);
+            }
+
+            return;
+        }
+    }
+
     let (mut connection_reader, mut connection_writer) = socket.into_split();
-    let (message_channel_sender, mut message_channel_reader) = mpsc::channel::<Arc<S2c>>(16);
+    let (message_channel_sender, mut message_channel_reader) =
+        mpsc::channel::<Arc<S2c>>(CLIENT_QUEUE_LEN);
+    let (event_sender, mut event_receiver) = mpsc::channel::<Arc<S2c>>(EVENT_QUEUE_LEN);
 
     let client_id = generate_client_id();
-    get_clients()
-        .lock()
-        .await
-        .insert(client_id, message_channel_sender.clone());
+    let connection = Arc::new(Mutex::new(client::Connection::new(client_id, addr)));
+    let (kick_sender, kick_receiver) = oneshot::channel();
+    get_clients().lock().await.insert(
+        client_id,
+        Client {
+            events: event_sender,
+            connection: connection.clone(),
+            kick: Some(kick_sender),
+        },
+    );
 
-    let state = Mutex::new(State::default());
+    let outgoing = client::handle_outgoing(
+        &connection,
+        &mut connection_writer,
+        &mut message_channel_reader,
+        &mut event_receiver,
+    );
+    tokio::pin!(outgoing);
 
-    tokio::select!(
-        Err(e) = client::handle_incoming(&state, &mut connection_reader, &message_channel_sender) => {
+    let reason = tokio::select!(
+        Err(e) = client::handle_incoming(&connection, &mut connection_reader, &message_channel_sender) => {
             log::error!("Client crashed while handling incoming packet with an error: {}", e);
+            disconnect_reason(&e)
         },
-        Err(e) = client::handle_outgoing(&state, &mut connection_writer, &mut message_channel_reader) => {
+        Err(e) = &mut outgoing => {
+            // The outgoing side already told the client if it could
             log::error!("Client crashed while handling outgoing packets with an error: {}", e);
+            None
+        },
+        Ok(reason) = kick_receiver => {
+            log::info!("Client {} ({}) was kicked: {}", client_id, addr, reason);
+            Some(TextComponent::text(&reason))
         }
     );
+
+    get_clients().lock().await.remove(&client_id);
+
+    // Take the player off everyone else's tab list
+    let uuid = {
+        let connection = connection.lock().await;
+        (connection.state == State::Play)
+            .then(|| connection.uuid.clone())
+            .flatten()
+    };
+    if let Some(uuid) = uuid {
+        let packet = S2c::PlayerInfoRemove { uuids: vec![uuid] };
+        if let Err(e) = event::get_event_bus().broadcast(packet).await {
+            log::debug!(
+                "Failed to remove client {} from the tab list: {}",
+                client_id,
+                e
+            );
+        }
+    }
+
+    if let Some(reason) = reason {
+        let state = connection.lock().await.state;
+
+        if let Some(packet) = S2c::disconnect(state, reason) {
+            let _ = message_channel_sender.send(Arc::new(packet)).await;
+        }
+
+        // Let the writer flush what's queued, it stops once every sender is gone
+        drop(message_channel_sender);
+        if tokio::time::timeout(DISCONNECT_TIMEOUT, &mut outgoing)
+            .await
+            .is_err()
+        {
+            log::debug!(
+                "Client {} didn't take the disconnect packet in time",
+                client_id
+            );
+        }
+    }
+
+    log::debug!("Client {} ({}) disconnected", client_id, addr);
+}
+
+// The client hung up by itself, there's nobody to explain the error to
+fn disconnect_reason(error: &io::Error) -> Option<TextComponent> {
+    match error.kind() {
+        io::ErrorKind::UnexpectedEof
+        | io::ErrorKind::ConnectionReset
+        | io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::BrokenPipe => None,
+        _ => Some(TextComponent::text(&error.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tcp::{protocol, AsyncReadOwnExt, AsyncWriteOwnExt};
+    use crate::VarInt;
+    use std::net::Ipv4Addr;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    struct TestClient {
+        id: u32,
+        receiver: Receiver<Arc<S2c>>,
+        kick: oneshot::Receiver<String>,
+    }
+
+    async fn add_client(state: State, capacity: usize) -> TestClient {
+        let id = generate_client_id();
+        let mut connection = client::Connection::new(id, (Ipv4Addr::LOCALHOST, 25565).into());
+        connection.state = state;
+
+        let (sender, receiver) = mpsc::channel(capacity);
+        let (kick_sender, kick) = oneshot::channel();
+        get_clients().lock().await.insert(
+            id,
+            Client {
+                events: sender,
+                connection: Arc::new(Mutex::new(connection)),
+                kick: Some(kick_sender),
+            },
+        );
+
+        TestClient { id, receiver, kick }
+    }
+
+    // Runs the event loop on its own channel until every event has been handled
+    async fn run_events(events: Vec<Event>) {
+        let (sender, mut receiver) = mpsc::channel(events.len());
+        for event in events {
+            sender.send(event).await.unwrap();
+        }
+        drop(sender);
+
+        event_loop(&mut receiver).await;
+    }
+
+    async fn set_name(client: &TestClient, username: &str) {
+        let connection = get_clients().lock().await[&client.id].connection.clone();
+        connection.lock().await.username = Some(username.to_owned());
+    }
+
+    async fn remove_clients(clients: &[&TestClient]) {
+        let mut map = get_clients().lock().await;
+        for client in clients {
+            map.remove(&client.id);
+        }
+    }
+
+    fn keep_alive() -> Arc<S2c> {
+        Arc::new(S2c::KeepAlive { id: 1 })
+    }
+
+    #[tokio::test]
+    async fn broadcast_skips_clients_outside_play() {
+        let mut player = add_client(State::Play, 16).await;
+        let mut pinging = add_client(State::Status, 16).await;
+        let mut logging_in = add_client(State::Login, 16).await;
+
+        run_events(vec![Event::Broadcast {
+            packet: keep_alive(),
+        }])
+        .await;
+
+        assert!(player.receiver.try_recv().is_ok());
+        assert!(pinging.receiver.try_recv().is_err());
+        assert!(logging_in.receiver.try_recv().is_err());
+
+        let mut clients = get_clients().lock().await;
+        for id in [player.id, pinging.id, logging_in.id] {
+            clients.remove(&id);
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_client_is_kicked() {
+        let mut client = add_client(State::Login, 1).await;
+
+        run_events(vec![
+            Event::SendTo {
+                client_id: client.id,
+                packet: keep_alive(),
+            },
+            Event::SendTo {
+                client_id: client.id,
+                packet: keep_alive(),
+            },
+        ])
+        .await;
+
+        assert_eq!(client.kick.try_recv().unwrap(), SLOW_CLIENT_REASON);
+        assert!(client.receiver.try_recv().is_ok());
+        assert!(client.receiver.try_recv().is_err());
+
+        get_clients().lock().await.remove(&client.id);
+    }
+
+    // Keep alive replies broadcast latencies while joining players are still taking chunks
+    #[tokio::test]
+    async fn broadcasts_have_their_own_budget() {
+        let mut client = add_client(State::Play, EVENT_QUEUE_LEN).await;
+
+        let broadcasts = (0..CLIENT_QUEUE_LEN * 4)
+            .map(|_| Event::Broadcast {
+                packet: keep_alive(),
+            })
+            .collect();
+        run_events(broadcasts).await;
+
+        assert!(client.kick.try_recv().is_err());
+        let mut received = 0;
+        while client.receiver.try_recv().is_ok() {
+            received += 1;
+        }
+        assert!(received >= CLIENT_QUEUE_LEN * 4);
+
+        get_clients().lock().await.remove(&client.id);
+    }
+
+    #[tokio::test]
+    async fn closed_client_is_not_kicked() {
+        let TestClient {
+            id,
+            receiver,
+            mut kick,
+        } = add_client(State::Login, 1).await;
+        drop(receiver);
+
+        run_events(vec![Event::SendTo {
+            client_id: id,
+            packet: keep_alive(),
+        }])
+        .await;
+
+        assert!(kick.try_recv().is_err());
+
+        get_clients().lock().await.remove(&id);
+    }
+
+    #[test]
+    fn client_ids_are_unique() {
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| (0..1000).map(|_| generate_client_id()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut ids: Vec<u32> = threads
+            .into_iter()
+            .flat_map(|thread| thread.join().unwrap())
+            .collect();
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+
+        assert_eq!(ids.len(), count);
+    }
+
+    #[tokio::test]
+    async fn online_players_are_in_play() {
+        let player = add_client(State::Play, 16).await;
+        let logging_in = add_client(State::Login, 16).await;
+        let pinging = add_client(State::Status, 16).await;
+        set_name(&player, "online_players_player").await;
+        set_name(&logging_in, "online_players_joining").await;
+
+        let online: Vec<u32> = get_online_players()
+            .await
+            .iter()
+            .map(|info| info.id)
+            .collect();
+        assert!(online.contains(&player.id));
+        assert!(!online.contains(&logging_in.id));
+        assert!(!online.contains(&pinging.id));
+
+        let listed: Vec<u32> = list_clients().await.iter().map(|info| info.id).collect();
+        for client in [&player, &logging_in, &pinging] {
+            assert!(listed.contains(&client.id));
+            assert_eq!(get_client(client.id).await.unwrap().id, client.id);
+        }
+
+        remove_clients(&[&player, &logging_in, &pinging]).await;
+        assert!(get_client(player.id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn finds_players_by_name() {
+        let player = add_client(State::Play, 16).await;
+        let logging_in = add_client(State::Login, 16).await;
+        set_name(&player, "by_name_player").await;
+        set_name(&logging_in, "by_name_joining").await;
+
+        let found = get_client_by_name("by_name_player").await.unwrap();
+        assert_eq!(found.id, player.id);
+        assert_eq!(found.username.as_deref(), Some("by_name_player"));
+
+        // Names are only taken once the login finished
+        assert!(get_client_by_name("by_name_joining").await.is_none());
+        assert!(get_client_by_name("BY_NAME_PLAYER").await.is_none());
+        assert!(get_client_by_name("by_name_nobody").await.is_none());
+
+        remove_clients(&[&player, &logging_in]).await;
+        assert!(get_client_by_name("by_name_player").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn disconnected_clients_are_removed() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (socket, addr) = listener.accept().await.unwrap();
+
+        // The first byte tells a legacy ping apart, the client is only added after it
+        client.write_all(&[0x10]).await.unwrap();
+        let handler = tokio::spawn(handle_connection(socket, addr));
+
+        let find = || async {
+            list_clients()
+                .await
+                .into_iter()
+                .find(|info| info.address == addr)
+        };
+
+        let info = loop {
+            match find().await {
+                Some(info) => break info,
+                None => tokio::task::yield_now().await,
+            }
+        };
+        assert_eq!(info.state, State::Shake);
+
+        drop(client);
+        handler.await.unwrap();
+
+        assert!(find().await.is_none());
+        assert!(get_client(info.id).await.is_none());
+    }
+
+    #[test]
+    fn hang_ups_have_no_reason() {
+        for kind in [
+            io::ErrorKind::UnexpectedEof,
+            io::ErrorKind::ConnectionReset,
+            io::ErrorKind::ConnectionAborted,
+            io::ErrorKind::BrokenPipe,
+        ] {
+            assert!(disconnect_reason(&io::Error::new(kind, "gone")).is_none());
+        }
+    }
+
+    #[test]
+    fn errors_become_reasons() {
+        let error = io::Error::new(io::ErrorKind::InvalidData, "Bad packet");
+        assert_eq!(
+            disconnect_reason(&error),
+            Some(TextComponent::text("Bad packet"))
+        );
+
+        let error = io::Error::other("Verify token doesn't match");
+        assert_eq!(
+            disconnect_reason(&error).unwrap().text,
+            "Verify token doesn't match"
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_login_gets_login_disconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (socket, addr) = listener.accept().await.unwrap();
+        let handler = tokio::spawn(handle_connection(socket, addr));
+
+        let mut handshake = vec![];
+        handshake.write_var_int(0x00).await.unwrap();
+        handshake.write_var_int(761).await.unwrap();
+        handshake.write_string("localhost").await.unwrap();
+        handshake.write_u16(25565).await.unwrap();
+        handshake.write_var_int(2).await.unwrap();
+        client
+            .write_var_int(handshake.len() as VarInt)
+            .await
+            .unwrap();
+        client.write_all(&handshake).await.unwrap();
+
+        let len = client.read_var_int().await.unwrap();
+        let mut packet = vec![0; len as usize];
+        client.read_exact(&mut packet).await.unwrap();
+        handler.await.unwrap();
+
+        let mut packet = &packet[..];
+        assert_eq!(packet.read_var_int().await.unwrap(), 0x00);
+        let reason = TextComponent::text(&protocol::unsupported_message(761));
+        assert_eq!(packet.read_string().await.unwrap(), reason.to_json());
+    }
 }
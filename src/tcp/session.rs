@@ -0,0 +1,221 @@
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use std::io;
+use std::sync::OnceLock;
+
+use crate::log;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GameProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub properties: Vec<ProfileProperty>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProfileProperty {
+    pub name: String,
+    pub value: String,
+    pub signature: Option<String>,
+}
+
+impl GameProfile {
+    pub fn uuid(&self) -> io::Result<Vec<u8>> {
+        let uuid = uuid::Uuid::try_parse(&self.id).map_err(io::Error::other)?;
+
+        Ok(uuid.as_bytes().to_vec())
+    }
+}
+
+fn get_http_client() -> &'static reqwest::Client {
+    static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+    HTTP_CLIENT.get_or_init(reqwest::Client::new)
+}
+
+// Minecraft's SHA-1 digest, printed as a signed two's complement hex number
+pub fn server_hash(server_id: &str, shared_secret: &[u8], public_key: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(server_id.as_bytes());
+    hasher.update(shared_secret);
+    hasher.update(public_key);
+    let mut digest = hasher.finalize().to_vec();
+
+    let negative = digest[0] & 0x80 != 0;
+    if negative {
+        let mut carry = true;
+        for byte in digest.iter_mut().rev() {
+            *byte = !*byte;
+            if carry {
+                let (value, overflow) = byte.overflowing_add(1);
+                *byte = value;
+                carry = overflow;
+            }
+        }
+    }
+
+    let hex = digest
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    let hex = hex.trim_start_matches('0');
+
+    if negative {
+        format!("-{}", hex)
+    } else {
+        hex.to_owned()
+    }
+}
+
+// Returns None when the session server doesn't know about the join
+pub async fn has_joined(
+    base_url: &str,
+    username: &str,
+    server_hash: &str,
+) -> io::Result<Option<GameProfile>> {
+    let url = format!(
+        "{}/session/minecraft/hasJoined",
+        base_url.trim_end_matches('/')
+    );
+    log::debug!("Authenticating {} against {}", username, url);
+
+    let response = get_http_client()
+        .get(url)
+        .query(&[("username", username), ("serverId", server_hash)])
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+
+    match response.status() {
+        reqwest::StatusCode::OK => {
+            let profile = response
+                .json::<GameProfile>()
+                .await
+                .map_err(io::Error::other)?;
+
+            Ok(Some(profile))
+        }
+        reqwest::StatusCode::NO_CONTENT => Ok(None),
+        status => Err(io::Error::other(format!(
+            "Session server answered with status {}",
+            status
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    // Answers a single request with the given status and body, handing back the request line
+    async fn mock_session_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let mut request = vec![];
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).await.unwrap();
+                assert!(read > 0, "Request ended early");
+                request.extend_from_slice(&buffer[..read]);
+            }
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+
+            let request = String::from_utf8(request).unwrap();
+            request.lines().next().unwrap().to_owned()
+        });
+
+        (base_url, handle)
+    }
+
+    #[tokio::test]
+    async fn has_joined_returns_profile() {
+        let body = r#"{
+            "id": "069a79f444e94726a5befca90e38aaf5",
+            "name": "Notch",
+            "properties": [{"name": "textures", "value": "e30=", "signature": "c2ln"}]
+        }"#;
+        let (base_url, request) = mock_session_server("200 OK", body).await;
+
+        let profile = has_joined(&format!("{}/", base_url), "Notch", "-abc")
+            .await
+            .unwrap()
+            .expect("Profile is returned");
+
+        assert_eq!(
+            request.await.unwrap(),
+            "GET /session/minecraft/hasJoined?username=Notch&serverId=-abc HTTP/1.1"
+        );
+        assert_eq!(profile.name, "Notch");
+        assert_eq!(
+            profile.uuid().unwrap(),
+            [
+                0x06, 0x9a, 0x79, 0xf4, 0x44, 0xe9, 0x47, 0x26, 0xa5, 0xbe, 0xfc, 0xa9, 0x0e, 0x38,
+                0xaa, 0xf5
+            ]
+        );
+        assert_eq!(profile.properties.len(), 1);
+        assert_eq!(profile.properties[0].name, "textures");
+        assert_eq!(profile.properties[0].signature.as_deref(), Some("c2ln"));
+    }
+
+    #[tokio::test]
+    async fn has_joined_without_join() {
+        let (base_url, request) = mock_session_server("204 No Content", "").await;
+
+        let profile = has_joined(&base_url, "Notch", "abc").await.unwrap();
+
+        assert!(profile.is_none());
+        request.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn has_joined_rejects_malformed_body() {
+        let (base_url, request) = mock_session_server("200 OK", r#"{"name": "Notch"#).await;
+
+        assert!(has_joined(&base_url, "Notch", "abc").await.is_err());
+        request.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn has_joined_rejects_other_statuses() {
+        let (base_url, request) = mock_session_server("503 Service Unavailable", "").await;
+
+        assert!(has_joined(&base_url, "Notch", "abc").await.is_err());
+        request.await.unwrap();
+    }
+
+    // Digests from wiki.vg, which hash just the name
+    #[test]
+    fn server_hash_matches_minecraft() {
+        assert_eq!(
+            server_hash("Notch", &[], &[]),
+            "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48"
+        );
+        assert_eq!(
+            server_hash("jeb_", &[], &[]),
+            "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1"
+        );
+        assert_eq!(
+            server_hash("simon", &[], &[]),
+            "88e16a1019277b15d58faf0541e11910eb756f6"
+        );
+    }
+}
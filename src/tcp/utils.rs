@@ -1,3 +1,4 @@
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 pub fn generate_offline_uuid(name: &'_ str) -> Vec<u8> {
@@ -10,6 +11,13 @@ pub fn generate_offline_uuid(name: &'_ str) -> Vec<u8> {
     )
 }
 
+// The client only gets the first 8 bytes of the seed's SHA-256, used for biome blending
+pub fn hash_seed(seed: i64) -> u64 {
+    let digest = Sha256::digest(seed.to_le_bytes());
+
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+}
+
 #[macro_export]
 macro_rules! measure {
     ($name:expr, $e:expr) => {{